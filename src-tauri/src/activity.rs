@@ -6,7 +6,11 @@
 //! - Cross-platform support for Windows, macOS, and Linux
 
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 
 /// Represents a detected running application
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +25,24 @@ pub struct DetectedActivity {
     pub window_title: Option<String>,
     /// When the activity started (Unix timestamp in ms)
     pub started_at: u64,
+    /// Now-playing track title, for Listening/Watching activities
+    pub details: Option<String>,
+    /// Now-playing artist, for Listening/Watching activities
+    pub state: Option<String>,
+    /// Playback start/end, derived from the track's position and duration
+    pub timestamps: Option<ActivityTimestamps>,
+    /// Album art: an MPRIS `artUrl` or a cached file path
+    pub artwork: Option<String>,
+    /// Steam CDN header art, when the process resolved to an installed
+    /// Steam app
+    pub icon_url: Option<String>,
+}
+
+/// Playback start/end timestamps (Unix ms), for rendering a progress bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityTimestamps {
+    pub start: Option<u64>,
+    pub end: Option<u64>,
 }
 
 /// System idle information
@@ -32,320 +54,287 @@ pub struct IdleStatus {
     pub is_idle: bool,
     /// Whether the screen is locked
     pub screen_locked: bool,
+    /// Names of active idle inhibitors (e.g. "audio_playback"). Non-empty
+    /// keeps `is_idle` false even past the threshold.
+    pub inhibited_by: Vec<String>,
 }
 
-/// Known applications to detect for rich presence
-static KNOWN_APPS: &[(&str, &str, u8)] = &[
-    // Games (type 0 = Playing)
-    ("steam", "Steam", 0),
-    ("minecraft", "Minecraft", 0),
-    ("javaw", "Minecraft", 0),
-    ("league of legends", "League of Legends", 0),
-    ("leagueclient", "League of Legends", 0),
-    ("valorant", "VALORANT", 0),
-    ("csgo", "Counter-Strike", 0),
-    ("cs2", "Counter-Strike 2", 0),
-    ("dota2", "Dota 2", 0),
-    ("overwatch", "Overwatch", 0),
-    ("fortnite", "Fortnite", 0),
-    ("roblox", "Roblox", 0),
-    ("gta5", "GTA V", 0),
-    ("gtav", "GTA V", 0),
-    ("cyberpunk2077", "Cyberpunk 2077", 0),
-    ("eldenring", "Elden Ring", 0),
-    ("baldur", "Baldur's Gate 3", 0),
-    ("bg3", "Baldur's Gate 3", 0),
-    ("wow", "World of Warcraft", 0),
-    ("ffxiv", "Final Fantasy XIV", 0),
-    ("destiny2", "Destiny 2", 0),
-    ("apex", "Apex Legends", 0),
-    ("rust", "Rust", 0),
-    ("terraria", "Terraria", 0),
-    ("starcraft", "StarCraft", 0),
-    ("diablo", "Diablo", 0),
-    ("hearthstone", "Hearthstone", 0),
-    ("fallout", "Fallout", 0),
-    ("skyrim", "Skyrim", 0),
-    ("witcher", "The Witcher", 0),
-
-    // Music (type 2 = Listening)
-    ("spotify", "Spotify", 2),
-    ("music", "Apple Music", 2),
-    ("itunes", "iTunes", 2),
-    ("tidal", "Tidal", 2),
-    ("deezer", "Deezer", 2),
-    ("soundcloud", "SoundCloud", 2),
-    ("amazon music", "Amazon Music", 2),
-    ("vlc", "VLC", 2),
-    ("foobar", "foobar2000", 2),
-    ("musicbee", "MusicBee", 2),
-
-    // Video (type 3 = Watching)
-    ("netflix", "Netflix", 3),
-    ("plex", "Plex", 3),
-    ("mpv", "Video", 3),
-    ("kodi", "Kodi", 3),
-    ("obs", "OBS Studio", 1), // Streaming
-    ("streamlabs", "Streamlabs", 1),
-
-    // Development tools (type 0 = Playing/Using)
-    ("code", "Visual Studio Code", 0),
-    ("code - insiders", "VS Code Insiders", 0),
-    ("cursor", "Cursor", 0),
-    ("webstorm", "WebStorm", 0),
-    ("intellij", "IntelliJ IDEA", 0),
-    ("pycharm", "PyCharm", 0),
-    ("rider", "Rider", 0),
-    ("android studio", "Android Studio", 0),
-    ("xcode", "Xcode", 0),
-    ("sublime", "Sublime Text", 0),
-    ("atom", "Atom", 0),
-    ("vim", "Vim", 0),
-    ("nvim", "Neovim", 0),
-    ("emacs", "Emacs", 0),
-];
-
 /// Default idle threshold in seconds (5 minutes)
 const DEFAULT_IDLE_THRESHOLD: u64 = 300;
 
+/// The cached `sysinfo::System` process table, refreshed on every poll
+/// instead of being rebuilt from scratch so `get_running_activities` stays
+/// cheap enough to call on a short interval
+static SYSTEM: Mutex<Option<System>> = Mutex::new(None);
+
 /// Get the list of detected activities (running apps that we care about)
 #[tauri::command]
-pub fn get_running_activities() -> Vec<DetectedActivity> {
-    let mut activities = Vec::new();
+pub fn get_running_activities(app: tauri::AppHandle) -> Vec<DetectedActivity> {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis() as u64;
 
-    #[cfg(target_os = "windows")]
-    {
-        activities = get_windows_activities(now);
-    }
+    get_process_activities(&app, now)
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        activities = get_macos_activities(now);
-    }
+/// Enumerate running processes in-process via `sysinfo` and match them
+/// against the active detection rules, replacing the old per-platform
+/// shell-outs
+fn get_process_activities<R: tauri::Runtime>(app: &tauri::AppHandle<R>, now: u64) -> Vec<DetectedActivity> {
+    let mut guard = SYSTEM.lock().unwrap();
+    let system = guard.get_or_insert_with(|| {
+        System::new_with_specifics(RefreshKind::new().with_processes(ProcessRefreshKind::everything()))
+    });
+    system.refresh_processes();
+
+    let Some(rules_state) = app.try_state::<crate::detection_rules::DetectionRulesState>() else {
+        return Vec::new();
+    };
+    let rules = rules_state.lock().unwrap();
+
+    let steam_apps = app
+        .try_state::<crate::steam::SteamLibraryState>()
+        .map(|state| state.lock().unwrap().clone())
+        .unwrap_or_default();
 
-    #[cfg(target_os = "linux")]
-    {
-        activities = get_linux_activities(now);
+    let mut activities = Vec::new();
+
+    for process in system.processes().values() {
+        let process_name = process.name().to_lowercase();
+        let exe_path = process.exe();
+        let exe_name = exe_path
+            .and_then(|path| path.file_stem())
+            .and_then(|stem| stem.to_str())
+            .map(|s| s.to_lowercase());
+
+        let matched = match_process(&rules, &steam_apps, &process_name, exe_path, exe_name.as_deref(), now);
+
+        if let Some(activity) = matched {
+            activities.push(activity);
+        }
     }
 
+    drop(rules);
+    enrich_with_now_playing(&mut activities, now);
+
     activities
 }
 
-/// Get idle status
-#[tauri::command]
-pub fn get_idle_status() -> IdleStatus {
-    let idle_seconds = get_system_idle_seconds();
-    
-    IdleStatus {
-        idle_seconds,
-        is_idle: idle_seconds > DEFAULT_IDLE_THRESHOLD,
-        screen_locked: is_screen_locked(),
+/// Match a process against, in order: the installed Steam library (for an
+/// accurate game name, AppID, and header art), then the active detection
+/// rules by process name or executable stem
+fn match_process(
+    rules: &[crate::detection_rules::CompiledRule],
+    steam_apps: &[crate::steam::SteamApp],
+    process_name: &str,
+    exe_path: Option<&std::path::Path>,
+    exe_name: Option<&str>,
+    started_at: u64,
+) -> Option<DetectedActivity> {
+    if let Some(exe_path) = exe_path {
+        if let Some((name, _app_id, icon_url)) = crate::steam::match_installed_app(steam_apps, exe_path) {
+            return Some(DetectedActivity {
+                name,
+                process_name: process_name.to_string(),
+                activity_type: 0,
+                window_title: None,
+                started_at,
+                details: None,
+                state: None,
+                timestamps: None,
+                artwork: None,
+                icon_url: Some(icon_url),
+            });
+        }
     }
+
+    let (name, activity_type) = crate::detection_rules::match_activity(rules, process_name, None)
+        .or_else(|| exe_name.and_then(|exe| crate::detection_rules::match_activity(rules, exe, None)))?;
+
+    Some(DetectedActivity {
+        name,
+        process_name: process_name.to_string(),
+        activity_type,
+        window_title: None,
+        started_at,
+        details: None,
+        state: None,
+        timestamps: None,
+        artwork: None,
+        icon_url: None,
+    })
 }
 
-/// Get idle status with custom threshold
-#[tauri::command]
-pub fn get_idle_status_with_threshold(threshold_seconds: u64) -> IdleStatus {
-    let idle_seconds = get_system_idle_seconds();
-    
-    IdleStatus {
-        idle_seconds,
-        is_idle: idle_seconds > threshold_seconds,
-        screen_locked: is_screen_locked(),
-    }
+/// Attach now-playing track metadata to the first Listening/Watching
+/// activity, since MPRIS/SMTC/MediaRemote expose "the" current session
+/// rather than per-process metadata
+fn enrich_with_now_playing(activities: &mut [DetectedActivity], now: u64) {
+    let Some(activity) = activities
+        .iter_mut()
+        .find(|a| matches!(a.activity_type, 2 | 3))
+    else {
+        return;
+    };
+
+    let Some(meta) = crate::media::now_playing() else {
+        return;
+    };
+
+    activity.details = meta.title;
+    activity.state = meta.artist;
+    activity.artwork = meta.artwork;
+
+    let start = meta.position_ms.map(|position| now.saturating_sub(position));
+    let end = match (start, meta.duration_ms) {
+        (Some(start), Some(duration)) => Some(start + duration),
+        _ => None,
+    };
+    activity.timestamps = Some(ActivityTimestamps { start, end });
 }
 
 // =============================================================================
-// Platform-specific implementations
+// Background monitor: emits transition events instead of snapshot polling
 // =============================================================================
 
-#[cfg(target_os = "windows")]
-fn get_windows_activities(now: u64) -> Vec<DetectedActivity> {
-    use std::process::Command;
-    
-    let mut activities = Vec::new();
-    
-    // Use WMIC or PowerShell to get running processes
-    let output = Command::new("powershell")
-        .args([
-            "-NoProfile",
-            "-Command",
-            "Get-Process | Where-Object {$_.MainWindowTitle -ne ''} | Select-Object ProcessName, MainWindowTitle | ConvertTo-Json"
-        ])
-        .output();
-    
-    if let Ok(output) = output {
-        if let Ok(text) = String::from_utf8(output.stdout) {
-            if let Ok(procs) = serde_json::from_str::<Vec<serde_json::Value>>(&text) {
-                for proc in procs {
-                    let process_name = proc.get("ProcessName")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_lowercase();
-                    let window_title = proc.get("MainWindowTitle")
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.to_string());
-                    
-                    if let Some(activity) = match_known_app(&process_name, window_title, now) {
-                        activities.push(activity);
-                    }
-                }
-            }
-        }
-    }
-    
-    activities
+/// Default poll interval for the background activity/idle monitor
+const DEFAULT_MONITOR_INTERVAL_MS: u64 = 2000;
+
+/// How far a tick's gap must exceed three poll intervals before it's
+/// treated as a suspend/resume rather than scheduling jitter
+const WAKE_GAP_GRACE: Duration = Duration::from_secs(2);
+
+/// Holds the running flag for the background monitor thread, if started.
+/// `Some` means running; dropping/clearing it doesn't stop the thread by
+/// itself, the thread checks the flag on its own each tick.
+pub type ActivityMonitorState = Mutex<Option<Arc<AtomicBool>>>;
+
+fn unix_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
 }
 
-#[cfg(target_os = "macos")]
-fn get_macos_activities(now: u64) -> Vec<DetectedActivity> {
-    use std::process::Command;
-    
-    let mut activities = Vec::new();
-    
-    // Use AppleScript to get running applications
-    let output = Command::new("osascript")
-        .args([
-            "-e",
-            r#"tell application "System Events"
-                set appList to ""
-                repeat with p in (every process whose background only is false)
-                    set appList to appList & name of p & "||" & (name of front window of p) & "
-"
-                end repeat
-                return appList
-            end tell"#
-        ])
-        .output();
-    
-    if let Ok(output) = output {
-        if let Ok(text) = String::from_utf8(output.stdout) {
-            for line in text.lines() {
-                let parts: Vec<&str> = line.split("||").collect();
-                if parts.is_empty() { continue; }
-                
-                let process_name = parts[0].to_lowercase();
-                let window_title = parts.get(1).map(|s| s.to_string());
-                
-                if let Some(activity) = match_known_app(&process_name, window_title, now) {
-                    activities.push(activity);
-                }
-            }
-        }
+fn emit_event<R: Runtime, S: serde::Serialize + Clone>(app: &AppHandle<R>, event: &str, payload: S) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(event, payload);
     }
-    
-    activities
 }
 
-#[cfg(target_os = "linux")]
-fn get_linux_activities(now: u64) -> Vec<DetectedActivity> {
-    use std::process::Command;
-    
-    let mut activities = Vec::new();
-    
-    // Try wmctrl first, then fall back to xdotool, then ps
-    let output = Command::new("wmctrl")
-        .args(["-l", "-p"])
-        .output();
-    
-    let processes: Vec<(String, Option<String>)> = if let Ok(output) = output {
-        if output.status.success() {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .filter_map(|line| {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 5 {
-                        let window_title = parts[4..].join(" ");
-                        // Try to get process name from PID
-                        if let Ok(pid) = parts[2].parse::<u32>() {
-                            let cmdline = std::fs::read_to_string(format!("/proc/{}/comm", pid))
-                                .unwrap_or_default()
-                                .trim()
-                                .to_string();
-                            Some((cmdline.to_lowercase(), Some(window_title)))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        } else {
-            Vec::new()
-        }
-    } else {
-        // Fall back to ps
-        let ps_output = Command::new("ps")
-            .args(["aux"])
-            .output();
-        
-        if let Ok(output) = ps_output {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .skip(1)
-                .filter_map(|line| {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 11 {
-                        let cmd = parts[10..].join(" ");
-                        let process_name = cmd.split('/').last().unwrap_or(&cmd).to_lowercase();
-                        Some((process_name, None))
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        } else {
-            Vec::new()
-        }
-    };
-    
-    for (process_name, window_title) in processes {
-        if let Some(activity) = match_known_app(&process_name, window_title, now) {
-            activities.push(activity);
-        }
+/// Start the background monitor, if it isn't already running. Polls every
+/// `interval_ms` (default 2s) and emits `activity-started`/`activity-stopped`,
+/// `user-idle`/`user-active` (crossing `idle_threshold_seconds`, default
+/// 5 minutes), `screen-locked`/`screen-unlocked`, and `wake-from-sleep`.
+#[tauri::command]
+pub fn start_activity_monitor<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<ActivityMonitorState>,
+    interval_ms: Option<u64>,
+    idle_threshold_seconds: Option<u64>,
+) {
+    let mut guard = state.lock().unwrap();
+    if guard.is_some() {
+        return;
     }
-    
-    activities
+
+    let running = Arc::new(AtomicBool::new(true));
+    spawn_monitor(
+        app,
+        running.clone(),
+        interval_ms.unwrap_or(DEFAULT_MONITOR_INTERVAL_MS),
+        idle_threshold_seconds.unwrap_or(DEFAULT_IDLE_THRESHOLD),
+    );
+    *guard = Some(running);
 }
 
-/// Match a process against known applications
-fn match_known_app(process_name: &str, window_title: Option<String>, started_at: u64) -> Option<DetectedActivity> {
-    let process_lower = process_name.to_lowercase();
-    
-    for (pattern, name, activity_type) in KNOWN_APPS {
-        if process_lower.contains(pattern) {
-            return Some(DetectedActivity {
-                name: name.to_string(),
-                process_name: process_name.to_string(),
-                activity_type: *activity_type,
-                window_title,
-                started_at,
-            });
-        }
+/// Stop the background monitor, if running
+#[tauri::command]
+pub fn stop_activity_monitor(state: State<ActivityMonitorState>) {
+    if let Some(running) = state.lock().unwrap().take() {
+        running.store(false, Ordering::SeqCst);
     }
-    
-    // Also check window title for matches
-    if let Some(ref title) = window_title {
-        let title_lower = title.to_lowercase();
-        for (pattern, name, activity_type) in KNOWN_APPS {
-            if title_lower.contains(pattern) {
-                return Some(DetectedActivity {
-                    name: name.to_string(),
-                    process_name: process_name.to_string(),
-                    activity_type: *activity_type,
-                    window_title: Some(title.clone()),
-                    started_at,
-                });
+}
+
+fn spawn_monitor<R: Runtime>(app: AppHandle<R>, running: Arc<AtomicBool>, interval_ms: u64, idle_threshold_seconds: u64) {
+    std::thread::spawn(move || {
+        let mut last_activities: Vec<DetectedActivity> = Vec::new();
+        let mut was_idle = false;
+        let mut was_locked = is_screen_locked();
+        let mut last_tick = Instant::now();
+
+        while running.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(interval_ms));
+
+            let tick = Instant::now();
+            let gap = tick.duration_since(last_tick);
+            last_tick = tick;
+            if gap > Duration::from_millis(interval_ms) * 3 + WAKE_GAP_GRACE {
+                emit_event(&app, "wake-from-sleep", ());
+            }
+
+            let activities = get_process_activities(&app, unix_ms());
+
+            for activity in &activities {
+                let was_running = last_activities.iter().any(|a| a.process_name == activity.process_name);
+                if !was_running {
+                    emit_event(&app, "activity-started", activity.clone());
+                }
+            }
+            for activity in &last_activities {
+                let still_running = activities.iter().any(|a| a.process_name == activity.process_name);
+                if !still_running {
+                    emit_event(&app, "activity-stopped", activity.clone());
+                }
+            }
+            last_activities = activities;
+
+            let idle_seconds = get_system_idle_seconds();
+            let is_idle = idle_seconds > idle_threshold_seconds;
+            if is_idle != was_idle {
+                was_idle = is_idle;
+                let event = if is_idle { "user-idle" } else { "user-active" };
+                emit_event(&app, event, serde_json::json!({ "idle_seconds": idle_seconds }));
+            }
+
+            let locked = is_screen_locked();
+            if locked != was_locked {
+                was_locked = locked;
+                emit_event(&app, if locked { "screen-locked" } else { "screen-unlocked" }, ());
             }
         }
+    });
+}
+
+/// Get idle status
+#[tauri::command]
+pub fn get_idle_status(app: AppHandle) -> IdleStatus {
+    build_idle_status(&app, DEFAULT_IDLE_THRESHOLD)
+}
+
+/// Get idle status with custom threshold
+#[tauri::command]
+pub fn get_idle_status_with_threshold(app: AppHandle, threshold_seconds: u64) -> IdleStatus {
+    build_idle_status(&app, threshold_seconds)
+}
+
+/// `is_idle` only goes true when input has been idle past the threshold
+/// AND no configured inhibitor (audio playback, a fullscreen app, a CPU
+/// load floor) is currently active
+fn build_idle_status<R: Runtime>(app: &AppHandle<R>, threshold_seconds: u64) -> IdleStatus {
+    let idle_seconds = get_system_idle_seconds();
+
+    let inhibited_by = app
+        .try_state::<crate::idle_inhibitors::IdleInhibitorConfigState>()
+        .map(|state| crate::idle_inhibitors::active_inhibitors(&state.lock().unwrap()))
+        .unwrap_or_default();
+
+    IdleStatus {
+        idle_seconds,
+        is_idle: idle_seconds > threshold_seconds && inhibited_by.is_empty(),
+        screen_locked: is_screen_locked(),
+        inhibited_by,
     }
-    
-    None
 }
 
 // =============================================================================
@@ -395,31 +384,51 @@ fn get_system_idle_seconds() -> u64 {
     0
 }
 
+// `IOHIDSystem`'s `HIDIdleTime` read directly via IOKit, replacing the old
+// `ioreg -c IOHIDSystem -d 4` text-scrape: no process spawn per poll, and
+// a real CFNumber instead of parsing a formatted dump.
+//
+// Deliberately not objc2/objc2-foundation: `IOServiceGetMatchingService`,
+// `IORegistryEntryCreateCFProperty`, and `IOHIDSystem` are plain C IOKit
+// APIs with no Objective-C surface, so there's nothing for objc2's
+// binding generator to cover here (objc2-foundation wraps Foundation's
+// Objective-C classes, not IOKit). `io-kit-sys` + `core-foundation` are
+// the established safe-ish wrappers for this specific API, the same way
+// `core-foundation`/a raw `extern "C"` are used just below for
+// `CGSessionCopyCurrentDictionary`.
 #[cfg(target_os = "macos")]
 fn get_system_idle_seconds() -> u64 {
-    use std::process::Command;
-    
-    let output = Command::new("ioreg")
-        .args(["-c", "IOHIDSystem", "-d", "4"])
-        .output();
-    
-    if let Ok(output) = output {
-        if let Ok(text) = String::from_utf8(output.stdout) {
-            // Parse HIDIdleTime from ioreg output (value is in nanoseconds)
-            for line in text.lines() {
-                if line.contains("HIDIdleTime") {
-                    if let Some(value_start) = line.find('=') {
-                        let value_str = line[value_start + 1..].trim();
-                        if let Ok(ns) = value_str.parse::<u64>() {
-                            return ns / 1_000_000_000; // Convert to seconds
-                        }
-                    }
-                }
-            }
+    use core_foundation::base::TCFType;
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::CFString;
+    use io_kit_sys::keys::kIOMasterPortDefault;
+    use io_kit_sys::types::io_object_t;
+    use io_kit_sys::{IOObjectRelease, IOServiceGetMatchingService, IOServiceMatching};
+
+    unsafe {
+        let matching = IOServiceMatching(c"IOHIDSystem".as_ptr().cast());
+        let entry: io_object_t = IOServiceGetMatchingService(kIOMasterPortDefault, matching);
+        if entry == 0 {
+            return 0;
         }
+
+        let key = CFString::new("HIDIdleTime");
+        let property = io_kit_sys::IORegistryEntryCreateCFProperty(
+            entry,
+            key.as_concrete_TypeRef(),
+            core_foundation::base::kCFAllocatorDefault,
+            0,
+        );
+        IOObjectRelease(entry);
+
+        if property.is_null() {
+            return 0;
+        }
+
+        let number = CFNumber::wrap_under_create_rule(property.cast());
+        let idle_ns = number.to_i64().unwrap_or(0).max(0) as u64;
+        idle_ns / 1_000_000_000
     }
-    
-    0
 }
 
 #[cfg(target_os = "linux")]
@@ -465,24 +474,55 @@ fn is_screen_locked() -> bool {
     false
 }
 
+// `CGSessionCopyCurrentDictionary` has no binding in any CoreGraphics
+// crate (it's public but header-less), so it's declared directly against
+// the framework, replacing the old `python3 -c "import Quartz ..."`
+// shell-out — this removes the PyObjC dependency entirely.
+//
+// Deliberately not objc2/objc2-foundation here either: this is a plain C
+// function exported by CoreGraphics (not an Objective-C class/method), so
+// objc2's generated bindings have nothing to attach to — the framework
+// has to be linked and the symbol declared by hand either way. The
+// resulting `CFDictionaryRef` is then handled through `core-foundation`,
+// matching the crate already used for `get_system_idle_seconds` above.
+#[cfg(target_os = "macos")]
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGSessionCopyCurrentDictionary() -> core_foundation::dictionary::CFDictionaryRef;
+}
+
 #[cfg(target_os = "macos")]
 fn is_screen_locked() -> bool {
-    use std::process::Command;
-    
-    let output = Command::new("python3")
-        .args([
-            "-c",
-            "import Quartz; print(Quartz.CGSessionCopyCurrentDictionary().get('CGSSessionScreenIsLocked', 0))"
-        ])
-        .output();
-    
-    if let Ok(output) = output {
-        if let Ok(text) = String::from_utf8(output.stdout) {
-            return text.trim() == "1";
+    use core_foundation::base::TCFType;
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::CFString;
+
+    unsafe {
+        let session_dict_ref = CGSessionCopyCurrentDictionary();
+        if session_dict_ref.is_null() {
+            // No session dictionary at all (e.g. fast user switching) means
+            // the session is not the active one, which we treat as locked
+            return true;
         }
+
+        let session_dict: CFDictionary<CFString, core_foundation::base::CFType> =
+            CFDictionary::wrap_under_create_rule(session_dict_ref);
+
+        let Some(value) = session_dict.find(CFString::new("CGSSessionScreenIsLocked")) else {
+            return false;
+        };
+
+        if let Some(boolean) = value.downcast::<CFBoolean>() {
+            return boolean == CFBoolean::true_value();
+        }
+        if let Some(number) = value.downcast::<CFNumber>() {
+            return number.to_i64().unwrap_or(0) != 0;
+        }
+
+        false
     }
-    
-    false
 }
 
 #[cfg(target_os = "linux")]
@@ -534,8 +574,3 @@ fn get_system_idle_seconds() -> u64 {
 fn is_screen_locked() -> bool {
     false
 }
-
-#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-fn get_windows_activities(_now: u64) -> Vec<DetectedActivity> {
-    Vec::new()
-}
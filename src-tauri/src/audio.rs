@@ -1,5 +1,11 @@
 use tauri::command;
-use std::process::Command;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::Instant;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::SampleFormat;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 
 /// Audio device information
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -16,230 +22,452 @@ pub enum AudioDeviceType {
     Output,
 }
 
-/// Get list of available audio input devices (microphones)
+/// An available cpal host API (e.g. WASAPI/ASIO on Windows, ALSA/Pulse/JACK
+/// on Linux)
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct AudioHost {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+}
+
+/// The input/output device the app is currently pinned to. cpal has no
+/// concept of "the OS default device" you can change, so unlike the old
+/// `SwitchAudioSource`/`pactl` backend this doesn't touch the system mixer
+/// at all — it only records the selection. The input-level monitor and
+/// call audio routing take their device explicitly and don't read these.
+static SELECTED_INPUT_DEVICE: Mutex<Option<String>> = Mutex::new(None);
+static SELECTED_OUTPUT_DEVICE: Mutex<Option<String>> = Mutex::new(None);
+
+/// The host API selected via `set_audio_host`, if any. Falls back to
+/// `cpal::default_host()` when unset.
+static SELECTED_HOST: Mutex<Option<cpal::HostId>> = Mutex::new(None);
+
+/// Derive a stable device ID from its name so IDs survive devices being
+/// plugged in a different order, unlike an enumeration index
+fn device_id(name: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The host API device enumeration should currently use
+fn current_host() -> cpal::Host {
+    SELECTED_HOST
+        .lock()
+        .unwrap()
+        .and_then(|id| cpal::host_from_id(id).ok())
+        .unwrap_or_else(cpal::default_host)
+}
+
+/// List the audio host APIs available on this platform (e.g. WASAPI, ASIO,
+/// ALSA, Pulse, JACK)
 #[tauri::command]
-pub fn get_audio_input_devices() -> Result<Vec<AudioDevice>, String> {
-    #[cfg(target_os = "macos")]
-    {
-        // Use SwitchAudioSource or coreaudio directly
-        let output = Command::new("SwitchAudioSource")
-            .args(&["-a", "-t", "input"])
-            .output()
-            .map_err(|e| format!("Failed to get input devices: {}", e))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let current_output = Command::new("SwitchAudioSource")
-            .args(&["-c", "-t", "input"])
-            .output()
-            .map_err(|e| format!("Failed to get current input: {}", e))?;
-        
-        let current = String::from_utf8_lossy(&current_output.stdout).trim().to_string();
-        
-        let devices: Vec<AudioDevice> = stdout
-            .lines()
-            .filter(|line| !line.is_empty())
-            .enumerate()
-            .map(|(idx, name)| AudioDevice {
-                id: format!("input_{}", idx),
-                name: name.trim().to_string(),
-                is_default: name.trim() == current,
-                device_type: AudioDeviceType::Input,
-            })
-            .collect();
-        
-        Ok(devices)
-    }
-    
-    #[cfg(target_os = "windows")]
-    {
-        // On Windows, we'd use WASAPI or Core Audio APIs
-        // For now, return a placeholder
-        Ok(vec![
-            AudioDevice {
-                id: "default".to_string(),
-                name: "Default Microphone".to_string(),
-                is_default: true,
-                device_type: AudioDeviceType::Input,
-            },
-        ])
-    }
-    
-    #[cfg(target_os = "linux")]
-    {
-        // Use pactl or amixer
-        let output = Command::new("pactl")
-            .args(&["list", "sources", "short"])
-            .output()
-            .map_err(|e| format!("Failed to get input devices: {}", e))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut devices = vec![];
-        
-        for line in stdout.lines() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 2 {
-                let name = parts.get(1).unwrap_or(&"Unknown").to_string();
-                let id = parts.get(0).unwrap_or(&"0").to_string();
-                let is_default = line.contains("RUNNING") || line.contains("Default");
-                
-                devices.push(AudioDevice {
-                    id,
-                    name,
-                    is_default,
-                    device_type: AudioDeviceType::Input,
-                });
+pub fn list_audio_hosts() -> Vec<AudioHost> {
+    let default_id = cpal::default_host().id();
+
+    cpal::available_hosts()
+        .into_iter()
+        .map(|id| AudioHost {
+            id: format!("{:?}", id),
+            name: id.name().to_string(),
+            is_default: id == default_id,
+        })
+        .collect()
+}
+
+/// Switch which host API device enumeration resolves against
+#[tauri::command]
+pub fn set_audio_host(id: String) -> Result<(), String> {
+    let host_id = cpal::available_hosts()
+        .into_iter()
+        .find(|h| format!("{:?}", h) == id)
+        .ok_or_else(|| format!("Unknown audio host: {}", id))?;
+
+    // Make sure the host actually initializes (e.g. JACK may be listed but
+    // not running) before committing to it
+    cpal::host_from_id(host_id).map_err(|e| format!("Failed to switch to {}: {}", id, e))?;
+
+    *SELECTED_HOST.lock().unwrap() = Some(host_id);
+    Ok(())
+}
+
+/// Find a cpal input device by its derived ID
+pub(crate) fn find_input_device(id: &str) -> Option<cpal::Device> {
+    current_host()
+        .input_devices()
+        .ok()?
+        .find(|d| d.name().map(|n| device_id(&n) == id).unwrap_or(false))
+}
+
+/// Find a cpal output device by its derived ID
+pub(crate) fn find_output_device(id: &str) -> Option<cpal::Device> {
+    current_host()
+        .output_devices()
+        .ok()?
+        .find(|d| d.name().map(|n| device_id(&n) == id).unwrap_or(false))
+}
+
+/// The input device selected via `set_audio_input_device`, if any
+pub(crate) fn selected_input_device_id() -> Option<String> {
+    SELECTED_INPUT_DEVICE.lock().unwrap().clone()
+}
+
+/// How often the device watcher checks for topology changes. cpal has no
+/// native hotplug notification API (no equivalent of a CoreAudio property
+/// listener, `IMMNotificationClient`, or `pactl subscribe`), so this polls
+/// and diffs the enumerated device set instead.
+const DEVICE_WATCH_INTERVAL_MS: u64 = 250;
+
+/// A comparable snapshot of the current device topology
+fn device_snapshot() -> Vec<String> {
+    let host = current_host();
+
+    let mut names: Vec<String> = host
+        .input_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).map(|n| format!("in:{}", n)).collect())
+        .unwrap_or_default();
+
+    names.extend(
+        host.output_devices()
+            .map(|devices| devices.filter_map(|d| d.name().ok()).map(|n| format!("out:{}", n)).collect::<Vec<_>>())
+            .unwrap_or_default(),
+    );
+
+    names.sort();
+    names
+}
+
+/// Spawn a background watcher that emits `audio-devices-changed` (with a
+/// fresh input/output device list) whenever a device is plugged in or
+/// unplugged, so the frontend can refresh its pickers and fall back off a
+/// device that just disappeared
+pub fn spawn_device_watcher<R: Runtime>(app: AppHandle<R>) {
+    std::thread::spawn(move || {
+        let mut last = device_snapshot();
+
+        loop {
+            std::thread::sleep(std::time::Duration::from_millis(DEVICE_WATCH_INTERVAL_MS));
+
+            let current = device_snapshot();
+            if current == last {
+                continue;
+            }
+            last = current;
+
+            let inputs = get_audio_input_devices().unwrap_or_default();
+            let outputs = get_audio_output_devices().unwrap_or_default();
+
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.emit("audio-devices-changed", serde_json::json!({
+                    "inputs": inputs,
+                    "outputs": outputs,
+                }));
             }
         }
-        
-        if devices.is_empty() {
-            devices.push(AudioDevice {
-                id: "default".to_string(),
-                name: "Default Microphone".to_string(),
-                is_default: true,
-                device_type: AudioDeviceType::Input,
-            });
-        }
-        
-        Ok(devices)
+    });
+}
+
+/// Holds the live mic-test stream, if one is running. Kept in managed state
+/// so `stop_input_monitor` can drop it from a later, unrelated command call.
+pub type InputMonitorState = Mutex<Option<cpal::Stream>>;
+
+/// How often `input-level` events are emitted to the main window
+const INPUT_LEVEL_INTERVAL_MS: u128 = 50;
+
+/// Compute normalized RMS level (0.0-1.0) and a clipping flag from
+/// interleaved f32 samples, averaging across channels per frame
+fn input_level(samples: &[f32], channels: usize) -> (f32, bool) {
+    let channels = channels.max(1);
+    if samples.is_empty() {
+        return (0.0, false);
     }
+
+    let mut sum_sq = 0.0f32;
+    let mut peak = 0.0f32;
+    let mut frames = 0usize;
+
+    for frame in samples.chunks(channels) {
+        let mono = frame.iter().copied().sum::<f32>() / frame.len() as f32;
+        sum_sq += mono * mono;
+        peak = peak.max(mono.abs());
+        frames += 1;
+    }
+
+    let rms = (sum_sq / frames.max(1) as f32).sqrt();
+    (rms.min(1.0), peak >= 0.99)
 }
 
-/// Get list of available audio output devices (speakers/headphones)
-#[tauri::command]
-pub fn get_audio_output_devices() -> Result<Vec<AudioDevice>, String> {
-    #[cfg(target_os = "macos")]
-    {
-        let output = Command::new("SwitchAudioSource")
-            .args(&["-a", "-t", "output"])
-            .output()
-            .map_err(|e| format!("Failed to get output devices: {}", e))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let current_output = Command::new("SwitchAudioSource")
-            .args(&["-c", "-t", "output"])
-            .output()
-            .map_err(|e| format!("Failed to get current output: {}", e))?;
-        
-        let current = String::from_utf8_lossy(&current_output.stdout).trim().to_string();
-        
-        let devices: Vec<AudioDevice> = stdout
-            .lines()
-            .filter(|line| !line.is_empty())
-            .enumerate()
-            .map(|(idx, name)| AudioDevice {
-                id: format!("output_{}", idx),
-                name: name.trim().to_string(),
-                is_default: name.trim() == current,
-                device_type: AudioDeviceType::Output,
-            })
-            .collect();
-        
-        Ok(devices)
+/// Throttle-and-emit helper shared by all three sample-format stream
+/// callbacks below
+fn emit_input_level<R: Runtime>(
+    app: &AppHandle<R>,
+    last_emit: &Mutex<Instant>,
+    samples: &[f32],
+    channels: usize,
+) {
+    let mut last_emit = last_emit.lock().unwrap();
+    if last_emit.elapsed().as_millis() < INPUT_LEVEL_INTERVAL_MS {
+        return;
     }
-    
-    #[cfg(target_os = "windows")]
-    {
-        Ok(vec![
-            AudioDevice {
-                id: "default".to_string(),
-                name: "Default Speakers".to_string(),
-                is_default: true,
-                device_type: AudioDeviceType::Output,
-            },
-        ])
+    *last_emit = Instant::now();
+
+    let (level, clipping) = input_level(samples, channels);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("input-level", serde_json::json!({
+            "level": level,
+            "clipping": clipping,
+        }));
     }
-    
-    #[cfg(target_os = "linux")]
-    {
-        let output = Command::new("pactl")
-            .args(&["list", "sinks", "short"])
-            .output()
-            .map_err(|e| format!("Failed to get output devices: {}", e))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut devices = vec![];
-        
-        for line in stdout.lines() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 2 {
-                let name = parts.get(1).unwrap_or(&"Unknown").to_string();
-                let id = parts.get(0).unwrap_or(&"0").to_string();
-                let is_default = line.contains("RUNNING") || parts.get(3) == Some(&"DEFAULT");
-                
-                devices.push(AudioDevice {
-                    id,
-                    name,
-                    is_default,
-                    device_type: AudioDeviceType::Output,
-                });
-            }
+}
+
+/// Start streaming mic input from `device_id` and emit `input-level` events
+/// on the main window roughly every 50ms, for a settings-screen VU meter
+#[tauri::command]
+pub fn start_input_monitor<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<InputMonitorState>,
+    device_id: String,
+) -> Result<(), String> {
+    let device = find_input_device(&device_id)
+        .ok_or_else(|| format!("Unknown input device: {}", device_id))?;
+
+    let config = device
+        .default_input_config()
+        .map_err(|e| format!("Failed to get input config: {}", e))?;
+
+    let sample_format = config.sample_format();
+    let channels = config.channels() as usize;
+    let stream_config: cpal::StreamConfig = config.into();
+
+    let err_fn = |err| eprintln!("Input monitor stream error: {}", err);
+    let last_emit = Mutex::new(Instant::now());
+
+    let stream = match sample_format {
+        SampleFormat::F32 => {
+            let app = app.clone();
+            device.build_input_stream(
+                &stream_config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    emit_input_level(&app, &last_emit, data, channels);
+                },
+                err_fn,
+                None,
+            )
+        }
+        SampleFormat::I16 => {
+            let app = app.clone();
+            device.build_input_stream(
+                &stream_config,
+                move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    let floats: Vec<f32> = data.iter().map(|s| *s as f32 / i16::MAX as f32).collect();
+                    emit_input_level(&app, &last_emit, &floats, channels);
+                },
+                err_fn,
+                None,
+            )
         }
-        
-        if devices.is_empty() {
-            devices.push(AudioDevice {
-                id: "default".to_string(),
-                name: "Default Speakers".to_string(),
-                is_default: true,
-                device_type: AudioDeviceType::Output,
-            });
+        SampleFormat::U16 => {
+            let app = app.clone();
+            device.build_input_stream(
+                &stream_config,
+                move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                    let floats: Vec<f32> = data
+                        .iter()
+                        .map(|s| (*s as f32 / u16::MAX as f32) * 2.0 - 1.0)
+                        .collect();
+                    emit_input_level(&app, &last_emit, &floats, channels);
+                },
+                err_fn,
+                None,
+            )
         }
-        
-        Ok(devices)
+        other => return Err(format!("Unsupported sample format: {:?}", other)),
     }
+    .map_err(|e| format!("Failed to build input stream: {}", e))?;
+
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start input stream: {}", e))?;
+
+    *state.lock().unwrap() = Some(stream);
+    Ok(())
 }
 
-/// Set the default audio input device
+/// Stop the mic-test stream started by `start_input_monitor`, if any
+#[tauri::command]
+pub fn stop_input_monitor(state: State<InputMonitorState>) -> Result<(), String> {
+    *state.lock().unwrap() = None;
+    Ok(())
+}
+
+/// Get list of available audio input devices (microphones), enumerated
+/// in-process via cpal so behavior is identical across WASAPI, CoreAudio,
+/// and ALSA/Pulse
+#[tauri::command]
+pub fn get_audio_input_devices() -> Result<Vec<AudioDevice>, String> {
+    let host = current_host();
+    let default_name = host.default_input_device().and_then(|d| d.name().ok());
+
+    let devices = host
+        .input_devices()
+        .map_err(|e| format!("Failed to enumerate input devices: {}", e))?;
+
+    Ok(devices
+        .filter_map(|device| device.name().ok())
+        .map(|name| AudioDevice {
+            id: device_id(&name),
+            is_default: Some(&name) == default_name.as_ref(),
+            name,
+            device_type: AudioDeviceType::Input,
+        })
+        .collect())
+}
+
+/// Get list of available audio output devices (speakers/headphones)
+#[tauri::command]
+pub fn get_audio_output_devices() -> Result<Vec<AudioDevice>, String> {
+    let host = current_host();
+    let default_name = host.default_output_device().and_then(|d| d.name().ok());
+
+    let devices = host
+        .output_devices()
+        .map_err(|e| format!("Failed to enumerate output devices: {}", e))?;
+
+    Ok(devices
+        .filter_map(|device| device.name().ok())
+        .map(|name| AudioDevice {
+            id: device_id(&name),
+            is_default: Some(&name) == default_name.as_ref(),
+            name,
+            device_type: AudioDeviceType::Output,
+        })
+        .collect())
+}
+
+/// Select which input device the app should use
 #[tauri::command]
 pub fn set_audio_input_device(device_id: String) -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        // Extract device name from id
-        let device_name = device_id.strip_prefix("input_")
-            .ok_or("Invalid device ID")?;
-        
-        let output = Command::new("SwitchAudioSource")
-            .args(&["-t", "input", "-s", device_name])
-            .output()
-            .map_err(|e| format!("Failed to set input device: {}", e))?;
-        
-        if !output.status.success() {
-            return Err("Failed to set input device".to_string());
-        }
-        
-        Ok(())
-    }
-    
-    #[cfg(not(target_os = "macos"))]
-    {
-        // Placeholder for other platforms
-        Ok(())
+    if find_input_device(&device_id).is_none() {
+        return Err(format!("Unknown input device: {}", device_id));
     }
+
+    *SELECTED_INPUT_DEVICE.lock().unwrap() = Some(device_id);
+    Ok(())
 }
 
-/// Set the default audio output device
+/// Select which output device the app should use
 #[tauri::command]
 pub fn set_audio_output_device(device_id: String) -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        let device_name = device_id.strip_prefix("output_")
-            .ok_or("Invalid device ID")?;
-        
-        let output = Command::new("SwitchAudioSource")
-            .args(&["-t", "output", "-s", device_name])
-            .output()
-            .map_err(|e| format!("Failed to set output device: {}", e))?;
-        
-        if !output.status.success() {
-            return Err("Failed to set output device".to_string());
+    if find_output_device(&device_id).is_none() {
+        return Err(format!("Unknown output device: {}", device_id));
+    }
+
+    *SELECTED_OUTPUT_DEVICE.lock().unwrap() = Some(device_id);
+    Ok(())
+}
+
+/// Raw CoreAudio bindings for system mixer volume/mute, replacing the old
+/// `osascript` shell-outs. cpal's `Host`/`Device` abstraction (used above
+/// for enumeration) has no volume API at all, so this talks to
+/// `AudioObjectGetPropertyData`/`AudioObjectSetPropertyData` directly —
+/// the same functions System Settings itself calls.
+#[cfg(target_os = "macos")]
+mod coreaudio_volume {
+    use std::os::raw::c_void;
+
+    type OsStatus = i32;
+    type AudioObjectId = u32;
+    type AudioObjectPropertySelector = u32;
+    type AudioObjectPropertyScope = u32;
+    type AudioObjectPropertyElement = u32;
+
+    #[repr(C)]
+    struct AudioObjectPropertyAddress {
+        selector: AudioObjectPropertySelector,
+        scope: AudioObjectPropertyScope,
+        element: AudioObjectPropertyElement,
+    }
+
+    const fn four_char_code(code: &[u8; 4]) -> u32 {
+        ((code[0] as u32) << 24) | ((code[1] as u32) << 16) | ((code[2] as u32) << 8) | (code[3] as u32)
+    }
+
+    const SYSTEM_OBJECT: AudioObjectId = 1;
+    const SCOPE_GLOBAL: AudioObjectPropertyScope = four_char_code(b"glob");
+    pub(super) const SCOPE_INPUT: AudioObjectPropertyScope = four_char_code(b"inpt");
+    pub(super) const SCOPE_OUTPUT: AudioObjectPropertyScope = four_char_code(b"outp");
+    const ELEMENT_MAIN: AudioObjectPropertyElement = 0;
+    const PROP_DEFAULT_INPUT_DEVICE: AudioObjectPropertySelector = four_char_code(b"dIn ");
+    const PROP_DEFAULT_OUTPUT_DEVICE: AudioObjectPropertySelector = four_char_code(b"dOut");
+    const PROP_VOLUME_SCALAR: AudioObjectPropertySelector = four_char_code(b"volm");
+    const PROP_MUTE: AudioObjectPropertySelector = four_char_code(b"mute");
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyData(
+            in_object_id: AudioObjectId,
+            in_address: *const AudioObjectPropertyAddress,
+            in_qualifier_data_size: u32,
+            in_qualifier_data: *const c_void,
+            io_data_size: *mut u32,
+            out_data: *mut c_void,
+        ) -> OsStatus;
+
+        fn AudioObjectSetPropertyData(
+            in_object_id: AudioObjectId,
+            in_address: *const AudioObjectPropertyAddress,
+            in_qualifier_data_size: u32,
+            in_qualifier_data: *const c_void,
+            in_data_size: u32,
+            in_data: *const c_void,
+        ) -> OsStatus;
+    }
+
+    fn get_property<T: Copy>(object_id: AudioObjectId, selector: AudioObjectPropertySelector, scope: AudioObjectPropertyScope, default: T) -> Result<T, String> {
+        let address = AudioObjectPropertyAddress { selector, scope, element: ELEMENT_MAIN };
+        let mut value = default;
+        let mut size = std::mem::size_of::<T>() as u32;
+        let status = unsafe {
+            AudioObjectGetPropertyData(object_id, &address, 0, std::ptr::null(), &mut size, &mut value as *mut T as *mut c_void)
+        };
+        if status != 0 {
+            return Err(format!("AudioObjectGetPropertyData failed with OSStatus {}", status));
         }
-        
-        Ok(())
+        Ok(value)
     }
-    
-    #[cfg(not(target_os = "macos"))]
-    {
+
+    fn set_property<T>(object_id: AudioObjectId, selector: AudioObjectPropertySelector, scope: AudioObjectPropertyScope, value: T) -> Result<(), String> {
+        let address = AudioObjectPropertyAddress { selector, scope, element: ELEMENT_MAIN };
+        let status = unsafe {
+            AudioObjectSetPropertyData(object_id, &address, 0, std::ptr::null(), std::mem::size_of::<T>() as u32, &value as *const T as *const c_void)
+        };
+        if status != 0 {
+            return Err(format!("AudioObjectSetPropertyData failed with OSStatus {}", status));
+        }
         Ok(())
     }
+
+    pub(super) fn default_device(scope: AudioObjectPropertyScope) -> Result<AudioObjectId, String> {
+        let selector = if scope == SCOPE_INPUT { PROP_DEFAULT_INPUT_DEVICE } else { PROP_DEFAULT_OUTPUT_DEVICE };
+        get_property(SYSTEM_OBJECT, selector, SCOPE_GLOBAL, 0u32)
+    }
+
+    pub(super) fn get_volume(device_id: AudioObjectId, scope: AudioObjectPropertyScope) -> Result<f32, String> {
+        get_property(device_id, PROP_VOLUME_SCALAR, scope, 0.0f32)
+    }
+
+    pub(super) fn set_volume(device_id: AudioObjectId, scope: AudioObjectPropertyScope, volume: f32) -> Result<(), String> {
+        set_property(device_id, PROP_VOLUME_SCALAR, scope, volume.clamp(0.0, 1.0))
+    }
+
+    pub(super) fn get_mute(device_id: AudioObjectId, scope: AudioObjectPropertyScope) -> Result<bool, String> {
+        Ok(get_property(device_id, PROP_MUTE, scope, 0u32)? != 0)
+    }
+
+    pub(super) fn set_mute(device_id: AudioObjectId, scope: AudioObjectPropertyScope, muted: bool) -> Result<(), String> {
+        set_property(device_id, PROP_MUTE, scope, if muted { 1u32 } else { 0u32 })
+    }
 }
 
 /// Get current input volume (0-100)
@@ -247,21 +475,14 @@ pub fn set_audio_output_device(device_id: String) -> Result<(), String> {
 pub fn get_input_volume() -> Result<u8, String> {
     #[cfg(target_os = "macos")]
     {
-        let output = Command::new("osascript")
-            .args(&["-e", "input volume of (get volume settings)"])
-            .output()
-            .map_err(|e| format!("Failed to get input volume: {}", e))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        stdout
-            .trim()
-            .parse::<u8>()
-            .map_err(|e| format!("Failed to parse volume: {}", e))
+        let device = coreaudio_volume::default_device(coreaudio_volume::SCOPE_INPUT)?;
+        let volume = coreaudio_volume::get_volume(device, coreaudio_volume::SCOPE_INPUT)?;
+        Ok((volume.clamp(0.0, 1.0) * 100.0).round() as u8)
     }
-    
+
     #[cfg(not(target_os = "macos"))]
     {
-        Ok(50) // Default
+        Err("Native input volume control isn't implemented on this platform yet".to_string())
     }
 }
 
@@ -269,24 +490,16 @@ pub fn get_input_volume() -> Result<u8, String> {
 #[tauri::command]
 pub fn set_input_volume(volume: u8) -> Result<(), String> {
     let volume = volume.min(100);
-    
+
     #[cfg(target_os = "macos")]
     {
-        let output = Command::new("osascript")
-            .args(&["-e", &format!("set volume input volume {}", volume)])
-            .output()
-            .map_err(|e| format!("Failed to set input volume: {}", e))?;
-        
-        if !output.status.success() {
-            return Err("Failed to set input volume".to_string());
-        }
-        
-        Ok(())
+        let device = coreaudio_volume::default_device(coreaudio_volume::SCOPE_INPUT)?;
+        coreaudio_volume::set_volume(device, coreaudio_volume::SCOPE_INPUT, volume as f32 / 100.0)
     }
-    
+
     #[cfg(not(target_os = "macos"))]
     {
-        Ok(())
+        Err("Native input volume control isn't implemented on this platform yet".to_string())
     }
 }
 
@@ -295,21 +508,14 @@ pub fn set_input_volume(volume: u8) -> Result<(), String> {
 pub fn get_output_volume() -> Result<u8, String> {
     #[cfg(target_os = "macos")]
     {
-        let output = Command::new("osascript")
-            .args(&["-e", "output volume of (get volume settings)"])
-            .output()
-            .map_err(|e| format!("Failed to get output volume: {}", e))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        stdout
-            .trim()
-            .parse::<u8>()
-            .map_err(|e| format!("Failed to parse volume: {}", e))
+        let device = coreaudio_volume::default_device(coreaudio_volume::SCOPE_OUTPUT)?;
+        let volume = coreaudio_volume::get_volume(device, coreaudio_volume::SCOPE_OUTPUT)?;
+        Ok((volume.clamp(0.0, 1.0) * 100.0).round() as u8)
     }
-    
+
     #[cfg(not(target_os = "macos"))]
     {
-        Ok(50) // Default
+        Err("Native output volume control isn't implemented on this platform yet".to_string())
     }
 }
 
@@ -317,24 +523,16 @@ pub fn get_output_volume() -> Result<u8, String> {
 #[tauri::command]
 pub fn set_output_volume(volume: u8) -> Result<(), String> {
     let volume = volume.min(100);
-    
+
     #[cfg(target_os = "macos")]
     {
-        let output = Command::new("osascript")
-            .args(&["-e", &format!("set volume output volume {}", volume)])
-            .output()
-            .map_err(|e| format!("Failed to set output volume: {}", e))?;
-        
-        if !output.status.success() {
-            return Err("Failed to set output volume".to_string());
-        }
-        
-        Ok(())
+        let device = coreaudio_volume::default_device(coreaudio_volume::SCOPE_OUTPUT)?;
+        coreaudio_volume::set_volume(device, coreaudio_volume::SCOPE_OUTPUT, volume as f32 / 100.0)
     }
-    
+
     #[cfg(not(target_os = "macos"))]
     {
-        Ok(())
+        Err("Native output volume control isn't implemented on this platform yet".to_string())
     }
 }
 
@@ -343,18 +541,13 @@ pub fn set_output_volume(volume: u8) -> Result<(), String> {
 pub fn is_output_muted() -> Result<bool, String> {
     #[cfg(target_os = "macos")]
     {
-        let output = Command::new("osascript")
-            .args(&["-e", "output muted of (get volume settings)"])
-            .output()
-            .map_err(|e| format!("Failed to get mute status: {}", e))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(stdout.trim() == "true")
+        let device = coreaudio_volume::default_device(coreaudio_volume::SCOPE_OUTPUT)?;
+        coreaudio_volume::get_mute(device, coreaudio_volume::SCOPE_OUTPUT)
     }
-    
+
     #[cfg(not(target_os = "macos"))]
     {
-        Ok(false)
+        Err("Native output mute control isn't implemented on this platform yet".to_string())
     }
 }
 
@@ -363,18 +556,12 @@ pub fn is_output_muted() -> Result<bool, String> {
 pub fn toggle_output_mute() -> Result<bool, String> {
     let currently_muted = is_output_muted()?;
     let new_state = !currently_muted;
-    
+
     #[cfg(target_os = "macos")]
     {
-        let output = Command::new("osascript")
-            .args(&["-e", &format!("set volume with output muted {}", new_state)])
-            .output()
-            .map_err(|e| format!("Failed to toggle mute: {}", e))?;
-        
-        if !output.status.success() {
-            return Err("Failed to toggle mute".to_string());
-        }
+        let device = coreaudio_volume::default_device(coreaudio_volume::SCOPE_OUTPUT)?;
+        coreaudio_volume::set_mute(device, coreaudio_volume::SCOPE_OUTPUT, new_state)?;
     }
-    
+
     Ok(new_state)
 }
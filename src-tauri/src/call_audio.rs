@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+/// Sample rate assumed for the call audio pipeline (matches the codecs'
+/// decode output used by the calling feature)
+const SAMPLE_RATE: u32 = 48_000;
+
+/// Tunable playback buffering for call audio, independent of the OS mixer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AudioBufferingConfig {
+    /// Target amount of decoded audio to keep queued, in milliseconds
+    pub average_buffering_ms: u32,
+    /// Size of the fade/drop batches used to correct drift from that target
+    pub batch_ms: u32,
+}
+
+impl Default for AudioBufferingConfig {
+    fn default() -> Self {
+        Self {
+            average_buffering_ms: 60,
+            batch_ms: 10,
+        }
+    }
+}
+
+/// Pins call audio playback to a specific output device, independent of
+/// whatever `set_audio_output_device` has the rest of the app pointed at
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomAudioDeviceConfig {
+    pub device_id: Option<String>,
+}
+
+static BUFFERING_CONFIG: Mutex<AudioBufferingConfig> = Mutex::new(AudioBufferingConfig {
+    average_buffering_ms: 60,
+    batch_ms: 10,
+});
+
+/// A ring buffer sitting between the network decode side and the cpal
+/// output stream. Maintains a target fill level of `average_buffering_ms`;
+/// on underrun it ramps the last sample down to silence within one batch instead
+/// of hard-zeroing (to avoid clicks), and on overrun past 2x target it
+/// drops the oldest batch to claw back latency.
+struct JitterBuffer {
+    samples: VecDeque<f32>,
+    channels: usize,
+    config: AudioBufferingConfig,
+    last_sample: f32,
+}
+
+impl JitterBuffer {
+    fn new(channels: usize, config: AudioBufferingConfig) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            channels: channels.max(1),
+            config,
+            last_sample: 0.0,
+        }
+    }
+
+    fn target_len(&self) -> usize {
+        (SAMPLE_RATE as u64 * self.config.average_buffering_ms as u64 / 1000) as usize * self.channels
+    }
+
+    fn batch_len(&self) -> usize {
+        ((SAMPLE_RATE as u64 * self.config.batch_ms as u64 / 1000) as usize * self.channels).max(1)
+    }
+
+    /// Queue freshly decoded samples from the network side
+    fn push(&mut self, decoded: &[f32]) {
+        self.samples.extend(decoded.iter().copied());
+
+        let overfull_at = self.target_len() * 2;
+        if self.samples.len() > overfull_at {
+            let drop = self.batch_len().min(self.samples.len());
+            self.samples.drain(..drop);
+        }
+    }
+
+    /// Fill a cpal output callback buffer
+    fn fill(&mut self, out: &mut [f32]) {
+        // A full-scale sample reaches zero after exactly one batch
+        let fade_step = 1.0 / self.batch_len() as f32;
+
+        for sample in out.iter_mut() {
+            if let Some(s) = self.samples.pop_front() {
+                self.last_sample = s;
+                *sample = s;
+            } else {
+                self.last_sample = self.last_sample.signum() * (self.last_sample.abs() - fade_step).max(0.0);
+                *sample = self.last_sample;
+            }
+        }
+    }
+}
+
+/// Holds the live call-audio output stream and its jitter buffer. There's
+/// only ever one active call, so this is a single slot rather than a map.
+#[derive(Default)]
+pub struct CallAudioState {
+    inner: Mutex<Option<CallAudioHandle>>,
+}
+
+struct CallAudioHandle {
+    stream: cpal::Stream,
+    buffer: std::sync::Arc<Mutex<JitterBuffer>>,
+}
+
+/// Get the current call audio buffering configuration
+#[tauri::command]
+pub fn get_audio_buffering() -> AudioBufferingConfig {
+    *BUFFERING_CONFIG.lock().unwrap()
+}
+
+/// Set the call audio buffering configuration. Takes effect the next time
+/// `start_call_audio` opens a stream.
+#[tauri::command]
+pub fn set_audio_buffering(config: AudioBufferingConfig) -> Result<(), String> {
+    *BUFFERING_CONFIG.lock().unwrap() = config;
+    Ok(())
+}
+
+/// Open the call audio output stream, optionally pinned to a specific
+/// device rather than the system default
+#[tauri::command]
+pub fn start_call_audio(
+    state: State<CallAudioState>,
+    device: CustomAudioDeviceConfig,
+) -> Result<(), String> {
+    let output_device = match device.device_id {
+        Some(id) => crate::audio::find_output_device(&id)
+            .ok_or_else(|| format!("Unknown output device: {}", id))?,
+        None => cpal::default_host()
+            .default_output_device()
+            .ok_or("No default output device")?,
+    };
+
+    let config = output_device
+        .default_output_config()
+        .map_err(|e| format!("Failed to get output config: {}", e))?;
+
+    let channels = config.channels() as usize;
+    let stream_config: cpal::StreamConfig = config.into();
+
+    let buffer = std::sync::Arc::new(Mutex::new(JitterBuffer::new(
+        channels,
+        *BUFFERING_CONFIG.lock().unwrap(),
+    )));
+
+    let callback_buffer = buffer.clone();
+    let stream = output_device
+        .build_output_stream(
+            &stream_config,
+            move |out: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                callback_buffer.lock().unwrap().fill(out);
+            },
+            |err| eprintln!("Call audio stream error: {}", err),
+            None,
+        )
+        .map_err(|e| format!("Failed to build call audio stream: {}", e))?;
+
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start call audio stream: {}", e))?;
+
+    *state.inner.lock().unwrap() = Some(CallAudioHandle { stream, buffer });
+    Ok(())
+}
+
+/// Stop call audio playback and drop the stream
+#[tauri::command]
+pub fn stop_call_audio(state: State<CallAudioState>) -> Result<(), String> {
+    *state.inner.lock().unwrap() = None;
+    Ok(())
+}
+
+/// Feed a batch of decoded call audio samples (interleaved f32, matching
+/// the active stream's channel count) into the jitter buffer
+#[tauri::command]
+pub fn push_call_audio(state: State<CallAudioState>, samples: Vec<f32>) -> Result<(), String> {
+    let guard = state.inner.lock().unwrap();
+    let handle = guard.as_ref().ok_or("Call audio is not running")?;
+    handle.buffer.lock().unwrap().push(&samples);
+    Ok(())
+}
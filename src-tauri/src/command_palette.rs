@@ -0,0 +1,87 @@
+//! Searchable command palette backed by a single registry of every
+//! menu-bar and tray action
+//!
+//! `create_menu`, `handle_menu_event`, and the tray menu each know about a
+//! handful of actions, but there was no single place to enumerate them for
+//! a fuzzy-search UI. This module lists every one of those action IDs once,
+//! and `invoke_command` funnels back into the exact same dispatch
+//! `on_menu_event` already uses, so the palette and the menu bar can never
+//! disagree about what an action does.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{keymap, shortcuts};
+
+/// A single palette-searchable action
+#[derive(Debug, Clone, Serialize)]
+pub struct Command {
+    pub id: String,
+    pub title: String,
+    pub accelerator: Option<String>,
+    pub category: String,
+}
+
+fn command(id: &str, title: &str, accelerator: Option<String>, category: &str) -> Command {
+    Command {
+        id: id.to_string(),
+        title: title.to_string(),
+        accelerator,
+        category: category.to_string(),
+    }
+}
+
+/// Every action currently reachable from `create_menu` or the tray menu,
+/// except the debug-build-only Developer Tools item.
+/// Accelerators are looked up live from `keymap`/`shortcuts` (whichever
+/// owns that action's binding) rather than hardcoded, so a rebind is
+/// reflected here immediately instead of showing a stale hint.
+#[tauri::command]
+pub fn list_commands(app: AppHandle) -> Vec<Command> {
+    vec![
+        command("new_chat", "New Chat", keymap::accelerator_for(&app, "new_chat"), "File"),
+        command("new_room", "New Room", keymap::accelerator_for(&app, "new_room"), "File"),
+        command("settings", "Settings...", keymap::accelerator_for(&app, "settings"), "File"),
+        command("toggle_sidebar", "Toggle Sidebar", keymap::accelerator_for(&app, "toggle_sidebar"), "View"),
+        command("zoom_in", "Zoom In", keymap::accelerator_for(&app, "zoom_in"), "View"),
+        command("zoom_out", "Zoom Out", keymap::accelerator_for(&app, "zoom_out"), "View"),
+        command("zoom_reset", "Actual Size", keymap::accelerator_for(&app, "zoom_reset"), "View"),
+        command("toggle_fullscreen", "Toggle Full Screen", keymap::accelerator_for(&app, "toggle_fullscreen"), "View"),
+        command("reload", "Reload", keymap::accelerator_for(&app, "reload"), "View"),
+        command("docs", "Documentation", None, "Help"),
+        command("report_issue", "Report Issue", None, "Help"),
+        command("check_updates", "Check for Updates...", None, "Help"),
+        command("about", "About Hearth", None, "Help"),
+        command("toggle_mute", "Toggle Mute", shortcuts::accelerator_for(&app, "toggle-mute"), "Tray"),
+        command("toggle_focus", "Toggle Focus Mode", shortcuts::accelerator_for(&app, "toggle-focus"), "Tray"),
+        command("show", "Show Window", shortcuts::accelerator_for(&app, "show-window"), "Tray"),
+        command("hide", "Hide Window", None, "Tray"),
+        command("start_at_login", "Toggle Start at Login", None, "Tray"),
+        command("quit", "Quit", None, "Tray"),
+    ]
+}
+
+/// Run a command by ID through the same dispatch `on_menu_event` uses
+#[tauri::command]
+pub fn invoke_command(app: AppHandle, id: String) -> Result<(), String> {
+    if !list_commands(app.clone()).iter().any(|c| c.id == id) {
+        return Err(format!("Unknown command: {}", id));
+    }
+
+    // `menu::handle_menu_event` already forwards the IDs the app menu shares
+    // with the tray (mute, focus mode, start at login) to the tray handler,
+    // so only the tray-only IDs go there directly; sending a shared ID to
+    // both would run its toggle twice
+    match id.as_str() {
+        "show" | "hide" | "quit" => crate::tray::handle_tray_menu_event(&app, &id),
+        _ => crate::menu::handle_menu_event(&app, &id),
+    }
+    Ok(())
+}
+
+/// Notify the frontend that the command palette should open
+pub fn show_command_palette(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("menu:command_palette", ());
+    }
+}
@@ -1,4 +1,4 @@
-use tauri::{Manager, Window, AppHandle};
+use tauri::{AppHandle, Manager, State, Window};
 use tauri_plugin_notification::NotificationExt;
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
@@ -62,13 +62,21 @@ pub async fn toggle_fullscreen(window: Window) -> Result<(), String> {
     window.set_fullscreen(!is_fullscreen).map_err(|e| e.to_string())
 }
 
-/// Show a system notification
+/// Show a system notification, unless muted, focus mode is active, or the
+/// DND schedule's quiet hours are in effect — in which case it's held in
+/// the replay queue instead of being dropped silently
 #[tauri::command]
 pub async fn show_notification(
     app: AppHandle,
+    queue_state: State<'_, crate::dnd::NotificationQueueState>,
     title: String,
     body: String,
 ) -> Result<(), String> {
+    if crate::dnd::is_suppressed(&app) {
+        crate::dnd::queue_notification(&queue_state, title, body);
+        return Ok(());
+    }
+
     app.notification()
         .builder()
         .title(&title)
@@ -153,6 +161,34 @@ pub async fn clipboard_clear(app: AppHandle) -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Decode a PNG (or any `image`-crate-supported format) and copy its raw
+/// RGBA pixels to the system clipboard
+#[tauri::command]
+pub async fn clipboard_write_image(app: AppHandle, path: String) -> Result<(), String> {
+    let rgba = image::open(&path).map_err(|e| e.to_string())?.to_rgba8();
+    let (width, height) = rgba.dimensions();
+
+    let image = tauri::image::Image::new_owned(rgba.into_raw(), width, height);
+    app.clipboard().write_image(&image).map_err(|e| e.to_string())
+}
+
+/// Read the clipboard's image content, saving it as a PNG into the
+/// screenshots dir and returning the new file's path
+#[tauri::command]
+pub async fn clipboard_read_image(app: AppHandle) -> Result<String, String> {
+    let clipboard_image = app.clipboard().read_image().map_err(|e| e.to_string())?;
+    let (width, height) = (clipboard_image.width(), clipboard_image.height());
+
+    let rgba = image::RgbaImage::from_raw(width, height, clipboard_image.rgba().to_vec())
+        .ok_or_else(|| "Clipboard image had an unexpected buffer size".to_string())?;
+
+    let dir = crate::screenshot::screenshots_dir(&app)?;
+    let filepath = crate::screenshot::timestamped_path(&dir, "png");
+    rgba.save_with_format(&filepath, image::ImageFormat::Png).map_err(|e| e.to_string())?;
+
+    Ok(filepath.to_string_lossy().to_string())
+}
+
 // ============================================================================
 // Quick Mute Commands
 // ============================================================================
@@ -187,10 +223,12 @@ pub fn set_mute(muted: bool) -> Result<bool, String> {
 // Tray Badge Commands
 // ============================================================================
 
-/// Update the tray icon with unread message count
+/// Update the tray icon and OS badge with the unread message count.
+/// `mention_count` is how many of those are mentions/DMs, which is what
+/// still shows up while focus mode is suppressing everything else.
 #[tauri::command]
-pub fn update_tray_badge(app: AppHandle, count: u32) -> Result<(), String> {
-    crate::tray::set_unread_count(&app, count).map_err(|e| e.to_string())
+pub fn update_tray_badge(app: AppHandle, count: u32, mention_count: u32) -> Result<(), String> {
+    crate::tray::set_unread_count(&app, count, mention_count).map_err(|e| e.to_string())
 }
 
 /// Get current unread count from tray
@@ -199,6 +237,18 @@ pub fn get_tray_badge() -> u32 {
     crate::tray::get_unread_count()
 }
 
+/// Enable or disable the OS dock/taskbar badge independent of the tray tooltip
+#[tauri::command]
+pub fn set_badges_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    crate::tray::set_badges_enabled(&app, enabled).map_err(|e| e.to_string())
+}
+
+/// Whether the OS badge is currently enabled
+#[tauri::command]
+pub fn are_badges_enabled() -> bool {
+    crate::tray::are_badges_enabled()
+}
+
 // ============================================================================
 // Focus Mode Commands
 // ============================================================================
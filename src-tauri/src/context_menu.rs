@@ -0,0 +1,152 @@
+//! Native right-click context menus for in-content targets
+//!
+//! The app menu bar and tray menu are built once and live for the app's
+//! lifetime. Context menus are different: the webview asks for one on
+//! right-click, naming what was clicked (`kind`) and which record it
+//! represents (`target_id`), and we build a throwaway `Menu` and pop it at
+//! the cursor. Every action routes back through a single emit so the
+//! frontend has one place to listen, mirroring `handle_menu_event`.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::{AppHandle, Emitter, LogicalPosition, Manager, Runtime};
+
+/// The in-flight popup's kind and target, set right before `popup_at` and
+/// consulted by `handle_menu_event` once the app's global menu-event
+/// callback reports which item was picked. A context menu is always modal
+/// to the click that opened it, so a single slot is enough.
+///
+/// Tauri's menu API has no "popup closed without a selection" callback, so
+/// dismissing a context menu (clicking elsewhere, pressing Escape) leaves
+/// this `Some(...)` forever otherwise, misrouting the next unrelated
+/// main-menu click as a stale context action. The timestamp lets
+/// `handle_menu_event` treat an entry as stale (and fall through to the
+/// main/tray handlers) once it's clearly outlived any popup still open.
+static PENDING_CONTEXT: Mutex<Option<(String, String, Instant)>> = Mutex::new(None);
+
+/// How long a pending context-menu selection is honored for. Generous
+/// relative to how long a user takes to pick an item or dismiss the popup.
+const PENDING_CONTEXT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The kind of thing that was right-clicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContextMenuKind {
+    Message,
+    Room,
+    User,
+    Sidebar,
+}
+
+impl ContextMenuKind {
+    fn parse(kind: &str) -> Result<Self, String> {
+        match kind {
+            "message" => Ok(Self::Message),
+            "room" => Ok(Self::Room),
+            "user" => Ok(Self::User),
+            "sidebar" => Ok(Self::Sidebar),
+            other => Err(format!("Unknown context menu kind: {}", other)),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Message => "message",
+            Self::Room => "room",
+            Self::User => "user",
+            Self::Sidebar => "sidebar",
+        }
+    }
+}
+
+/// Build the menu items for a given context menu kind. Item IDs are the
+/// action name only (e.g. "reply") — the kind and target are kept in
+/// `PENDING_CONTEXT` instead of the ID, since the same action (e.g.
+/// "copy") can appear in more than one kind.
+fn build_menu<R: Runtime>(
+    app: &AppHandle<R>,
+    kind: ContextMenuKind,
+) -> Result<Menu<R>, Box<dyn std::error::Error>> {
+    match kind {
+        ContextMenuKind::Message => Ok(Menu::with_items(
+            app,
+            &[
+                &MenuItem::with_id(app, "reply", "Reply", true, None::<&str>)?,
+                &MenuItem::with_id(app, "edit", "Edit", true, None::<&str>)?,
+                &MenuItem::with_id(app, "copy", "Copy", true, None::<&str>)?,
+                &MenuItem::with_id(app, "pin", "Pin", true, None::<&str>)?,
+                &PredefinedMenuItem::separator(app)?,
+                &MenuItem::with_id(app, "delete", "Delete", true, None::<&str>)?,
+            ],
+        )?),
+        ContextMenuKind::Room => Ok(Menu::with_items(
+            app,
+            &[
+                &MenuItem::with_id(app, "mark_read", "Mark Read", true, None::<&str>)?,
+                &MenuItem::with_id(app, "mute_room", "Mute Room", true, None::<&str>)?,
+                &PredefinedMenuItem::separator(app)?,
+                &MenuItem::with_id(app, "copy", "Copy Link", true, None::<&str>)?,
+                &PredefinedMenuItem::separator(app)?,
+                &MenuItem::with_id(app, "delete", "Leave Room", true, None::<&str>)?,
+            ],
+        )?),
+        ContextMenuKind::User => Ok(Menu::with_items(
+            app,
+            &[
+                &MenuItem::with_id(app, "reply", "Message", true, None::<&str>)?,
+                &MenuItem::with_id(app, "copy", "Copy User ID", true, None::<&str>)?,
+                &PredefinedMenuItem::separator(app)?,
+                &MenuItem::with_id(app, "mute_room", "Mute User", true, None::<&str>)?,
+            ],
+        )?),
+        ContextMenuKind::Sidebar => Ok(Menu::with_items(
+            app,
+            &[
+                &MenuItem::with_id(app, "mark_read", "Mark All Read", true, None::<&str>)?,
+                &PredefinedMenuItem::separator(app)?,
+                &MenuItem::with_id(app, "pin", "Collapse Section", true, None::<&str>)?,
+            ],
+        )?),
+    }
+}
+
+/// Show a native context menu for `kind` at the given window-relative
+/// coordinates, and route the chosen action back to the frontend as
+/// `context:<kind>:<action>` carrying `target_id`.
+#[tauri::command]
+pub fn show_context_menu(
+    app: AppHandle,
+    window: tauri::Window,
+    kind: String,
+    target_id: String,
+    x: f64,
+    y: f64,
+) -> Result<(), String> {
+    let kind = ContextMenuKind::parse(&kind)?;
+    let menu = build_menu(&app, kind).map_err(|e| e.to_string())?;
+
+    *PENDING_CONTEXT.lock().unwrap() = Some((kind.as_str().to_string(), target_id, Instant::now()));
+    menu.popup_at(window, LogicalPosition::new(x, y))
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Try to handle a menu event as a context-menu selection. Returns `true`
+/// if a context menu was pending and the event was routed, so callers (the
+/// app-wide `on_menu_event`) can fall back to the static menu/tray handlers
+/// otherwise.
+pub fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, action: &str) -> bool {
+    let context = PENDING_CONTEXT.lock().unwrap().take();
+    let (kind, target_id) = match context {
+        Some((kind, target_id, opened_at)) if opened_at.elapsed() < PENDING_CONTEXT_TIMEOUT => {
+            (kind, target_id)
+        }
+        _ => return false,
+    };
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(&format!("context:{}:{}", kind, action), target_id);
+    }
+    true
+}
@@ -1,91 +1,167 @@
 use tauri::{AppHandle, Manager, Runtime};
 use serde::{Deserialize, Serialize};
 
-/// Parsed deep link data
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeepLinkPayload {
-    /// The action type (chat, room, channel, server, invite, settings)
-    pub action: String,
-    /// The target ID (room id, channel id, server id, user id, etc.)
-    pub target: Option<String>,
-    /// Additional parameters
-    pub params: std::collections::HashMap<String, String>,
-}
-
-/// Parse a hearth:// URL into a DeepLinkPayload
-/// 
+/// A parsed `hearth://` deep link. Each variant carries exactly the fields
+/// that action needs, so callers match on structure instead of re-parsing a
+/// stringly-typed `action`/`target`/`params` triple.
+///
 /// Supported formats:
 /// - hearth://chat/:userId - Open DM with user
-/// - hearth://room/:roomId - Open a room  
+/// - hearth://room/:roomId - Open a room
 /// - hearth://channel/:channelId - Navigate to a specific channel
 /// - hearth://server/:serverId - Navigate to a specific server
 /// - hearth://server/:serverId/:channelId - Navigate to server + channel
 /// - hearth://invite/:code - Accept an invite
-/// - hearth://invite/:code?server=:serverId - Accept invite with server context
+/// - hearth://invite/:code?server=:serverId&ref=:ref - Accept invite with context
 /// - hearth://settings - Open settings
 /// - hearth://settings/:section - Open specific settings section
 /// - hearth://call/:callId - Join a voice call
-pub fn parse_deep_link(url: &str) -> Option<DeepLinkPayload> {
-    let url = url.trim();
-    
-    // Must start with hearth://
-    if !url.starts_with("hearth://") {
-        return None;
-    }
-
-    let path = &url[9..]; // Remove "hearth://"
-    let mut parts: Vec<&str> = path.split('?').collect();
-    let path_part = parts.remove(0);
-    
-    // Parse query params
-    let mut params = std::collections::HashMap::new();
-    if !parts.is_empty() {
-        let query = parts.join("?");
-        for pair in query.split('&') {
-            if let Some((key, value)) = pair.split_once('=') {
-                params.insert(
-                    urlencoding::decode(key).unwrap_or_default().to_string(),
-                    urlencoding::decode(value).unwrap_or_default().to_string(),
-                );
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DeepLinkAction {
+    Chat { user: String },
+    Room { id: String },
+    Channel { id: String },
+    Server { id: String, channel: Option<String> },
+    Invite {
+        code: String,
+        server: Option<String>,
+        #[serde(rename = "ref")]
+        ref_: Option<String>,
+    },
+    Settings { section: Option<String> },
+    Call { id: String },
+}
+
+impl TryFrom<&str> for DeepLinkAction {
+    type Error = String;
+
+    fn try_from(url: &str) -> Result<Self, Self::Error> {
+        let url = url.trim();
+
+        let path = url
+            .strip_prefix("hearth://")
+            .ok_or_else(|| format!("Not a hearth:// link: {}", url))?;
+
+        let mut parts: Vec<&str> = path.split('?').collect();
+        let path_part = parts.remove(0);
+
+        let mut params = std::collections::HashMap::new();
+        if !parts.is_empty() {
+            let query = parts.join("?");
+            for pair in query.split('&') {
+                if let Some((key, value)) = pair.split_once('=') {
+                    params.insert(
+                        urlencoding::decode(key).unwrap_or_default().to_string(),
+                        urlencoding::decode(value).unwrap_or_default().to_string(),
+                    );
+                }
             }
         }
-    }
 
-    // Parse path
-    let segments: Vec<&str> = path_part.split('/').filter(|s| !s.is_empty()).collect();
-    
-    if segments.is_empty() {
-        return None;
-    }
+        let segments: Vec<String> = path_part
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| urlencoding::decode(s).unwrap_or_default().to_string())
+            .collect();
+        let action = segments.first().ok_or("Deep link is missing an action")?.clone();
+        let target = segments.get(1).cloned();
 
-    let action = segments[0].to_string();
-    let target = segments.get(1).map(|s| s.to_string());
+        match action.as_str() {
+            "chat" => Ok(DeepLinkAction::Chat {
+                user: target.ok_or("chat link is missing a user id")?,
+            }),
+            "room" => Ok(DeepLinkAction::Room {
+                id: target.ok_or("room link is missing a room id")?,
+            }),
+            "channel" => Ok(DeepLinkAction::Channel {
+                id: target.ok_or("channel link is missing a channel id")?,
+            }),
+            "server" => Ok(DeepLinkAction::Server {
+                id: target.ok_or("server link is missing a server id")?,
+                channel: segments.get(2).cloned(),
+            }),
+            "invite" => Ok(DeepLinkAction::Invite {
+                code: target.ok_or("invite link is missing a code")?,
+                server: params.get("server").cloned(),
+                ref_: params.get("ref").cloned(),
+            }),
+            "settings" => Ok(DeepLinkAction::Settings { section: target }),
+            "call" => Ok(DeepLinkAction::Call {
+                id: target.ok_or("call link is missing a call id")?,
+            }),
+            other => Err(format!("Unknown deep link action: {}", other)),
+        }
+    }
+}
 
-    // Handle special case: server/:serverId/:channelId
-    // The third segment becomes a "channel" param
-    if action == "server" && segments.len() >= 3 {
-        params.insert("channel".to_string(), segments[2].to_string());
+impl DeepLinkAction {
+    /// Build the `hearth://` URL for this action, percent-encoding each
+    /// segment and query param, so share/invite links can be generated as
+    /// well as parsed
+    pub fn to_url(&self) -> String {
+        match self {
+            DeepLinkAction::Chat { user } => format!("hearth://chat/{}", urlencoding::encode(user)),
+            DeepLinkAction::Room { id } => format!("hearth://room/{}", urlencoding::encode(id)),
+            DeepLinkAction::Channel { id } => format!("hearth://channel/{}", urlencoding::encode(id)),
+            DeepLinkAction::Server { id, channel } => {
+                let base = format!("hearth://server/{}", urlencoding::encode(id));
+                match channel {
+                    Some(channel) => format!("{}/{}", base, urlencoding::encode(channel)),
+                    None => base,
+                }
+            }
+            DeepLinkAction::Invite { code, server, ref_ } => {
+                let mut url = format!("hearth://invite/{}", urlencoding::encode(code));
+                let mut query = Vec::new();
+                if let Some(server) = server {
+                    query.push(format!("server={}", urlencoding::encode(server)));
+                }
+                if let Some(ref_) = ref_ {
+                    query.push(format!("ref={}", urlencoding::encode(ref_)));
+                }
+                if !query.is_empty() {
+                    url.push('?');
+                    url.push_str(&query.join("&"));
+                }
+                url
+            }
+            DeepLinkAction::Settings { section } => match section {
+                Some(section) => format!("hearth://settings/{}", urlencoding::encode(section)),
+                None => "hearth://settings".to_string(),
+            },
+            DeepLinkAction::Call { id } => format!("hearth://call/{}", urlencoding::encode(id)),
+        }
     }
+}
+
+/// Parse a `hearth://` URL into a `DeepLinkAction`, or `None` if it's
+/// malformed or the action isn't recognized
+pub fn parse_deep_link(url: &str) -> Option<DeepLinkAction> {
+    DeepLinkAction::try_from(url).ok()
+}
 
-    Some(DeepLinkPayload {
-        action,
-        target,
-        params,
-    })
+/// Build a shareable `hearth://` URL for an action, e.g. for a "copy invite
+/// link" button
+#[tauri::command]
+pub fn build_deep_link(action: DeepLinkAction) -> String {
+    action.to_url()
 }
 
-/// Handle a deep link by emitting to the frontend
+/// Handle a deep link by emitting the parsed action to the frontend
 pub fn handle_deep_link<R: Runtime>(app: &AppHandle<R>, url: &str) {
-    if let Some(payload) = parse_deep_link(url) {
-        log::info!("Handling deep link: {:?}", payload);
-        
-        // Show and focus the window
-        if let Some(window) = app.get_webview_window("main") {
-            let _ = window.show();
-            let _ = window.set_focus();
-            
-            // Emit the deep link event to frontend
-            let _ = window.emit("deeplink", payload);
+    match DeepLinkAction::try_from(url) {
+        Ok(action) => {
+            log::info!("Handling deep link: {:?}", action);
+
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+                let _ = window.emit("deeplink", action);
+            }
+        }
+        Err(err) => {
+            log::warn!("Rejected malformed deep link {}: {}", url, err);
         }
     }
 }
@@ -96,74 +172,136 @@ mod tests {
 
     #[test]
     fn test_parse_chat_link() {
-        let payload = parse_deep_link("hearth://chat/user123").unwrap();
-        assert_eq!(payload.action, "chat");
-        assert_eq!(payload.target, Some("user123".to_string()));
+        let action = parse_deep_link("hearth://chat/user123").unwrap();
+        assert_eq!(action, DeepLinkAction::Chat { user: "user123".to_string() });
     }
 
     #[test]
     fn test_parse_room_link() {
-        let payload = parse_deep_link("hearth://room/abc-def-ghi").unwrap();
-        assert_eq!(payload.action, "room");
-        assert_eq!(payload.target, Some("abc-def-ghi".to_string()));
+        let action = parse_deep_link("hearth://room/abc-def-ghi").unwrap();
+        assert_eq!(action, DeepLinkAction::Room { id: "abc-def-ghi".to_string() });
     }
 
     #[test]
     fn test_parse_channel_link() {
-        let payload = parse_deep_link("hearth://channel/chan-123").unwrap();
-        assert_eq!(payload.action, "channel");
-        assert_eq!(payload.target, Some("chan-123".to_string()));
+        let action = parse_deep_link("hearth://channel/chan-123").unwrap();
+        assert_eq!(action, DeepLinkAction::Channel { id: "chan-123".to_string() });
     }
 
     #[test]
     fn test_parse_server_link() {
-        let payload = parse_deep_link("hearth://server/server-456").unwrap();
-        assert_eq!(payload.action, "server");
-        assert_eq!(payload.target, Some("server-456".to_string()));
+        let action = parse_deep_link("hearth://server/server-456").unwrap();
+        assert_eq!(action, DeepLinkAction::Server { id: "server-456".to_string(), channel: None });
     }
 
     #[test]
     fn test_parse_server_channel_link() {
-        let payload = parse_deep_link("hearth://server/server-456/chan-789").unwrap();
-        assert_eq!(payload.action, "server");
-        assert_eq!(payload.target, Some("server-456".to_string()));
-        assert_eq!(payload.params.get("channel"), Some(&"chan-789".to_string()));
+        let action = parse_deep_link("hearth://server/server-456/chan-789").unwrap();
+        assert_eq!(
+            action,
+            DeepLinkAction::Server { id: "server-456".to_string(), channel: Some("chan-789".to_string()) }
+        );
     }
 
     #[test]
     fn test_parse_invite_link() {
-        let payload = parse_deep_link("hearth://invite/ABCD1234?ref=email").unwrap();
-        assert_eq!(payload.action, "invite");
-        assert_eq!(payload.target, Some("ABCD1234".to_string()));
-        assert_eq!(payload.params.get("ref"), Some(&"email".to_string()));
+        let action = parse_deep_link("hearth://invite/ABCD1234?ref=email").unwrap();
+        assert_eq!(
+            action,
+            DeepLinkAction::Invite { code: "ABCD1234".to_string(), server: None, ref_: Some("email".to_string()) }
+        );
     }
 
     #[test]
     fn test_parse_invite_with_server() {
-        let payload = parse_deep_link("hearth://invite/XYZ789?server=server-123").unwrap();
-        assert_eq!(payload.action, "invite");
-        assert_eq!(payload.target, Some("XYZ789".to_string()));
-        assert_eq!(payload.params.get("server"), Some(&"server-123".to_string()));
+        let action = parse_deep_link("hearth://invite/XYZ789?server=server-123").unwrap();
+        assert_eq!(
+            action,
+            DeepLinkAction::Invite { code: "XYZ789".to_string(), server: Some("server-123".to_string()), ref_: None }
+        );
     }
 
     #[test]
     fn test_parse_settings_link() {
-        let payload = parse_deep_link("hearth://settings").unwrap();
-        assert_eq!(payload.action, "settings");
-        assert_eq!(payload.target, None);
+        let action = parse_deep_link("hearth://settings").unwrap();
+        assert_eq!(action, DeepLinkAction::Settings { section: None });
     }
 
     #[test]
     fn test_parse_settings_section() {
-        let payload = parse_deep_link("hearth://settings/notifications").unwrap();
-        assert_eq!(payload.action, "settings");
-        assert_eq!(payload.target, Some("notifications".to_string()));
+        let action = parse_deep_link("hearth://settings/notifications").unwrap();
+        assert_eq!(action, DeepLinkAction::Settings { section: Some("notifications".to_string()) });
     }
 
     #[test]
     fn test_parse_call_link() {
-        let payload = parse_deep_link("hearth://call/call-abc-123").unwrap();
-        assert_eq!(payload.action, "call");
-        assert_eq!(payload.target, Some("call-abc-123".to_string()));
+        let action = parse_deep_link("hearth://call/call-abc-123").unwrap();
+        assert_eq!(action, DeepLinkAction::Call { id: "call-abc-123".to_string() });
+    }
+
+    #[test]
+    fn test_rejects_unknown_action() {
+        assert!(parse_deep_link("hearth://frobnicate/123").is_none());
+    }
+
+    #[test]
+    fn test_rejects_non_hearth_scheme() {
+        assert!(parse_deep_link("https://example.com").is_none());
+    }
+
+    fn round_trip(action: DeepLinkAction) {
+        let url = action.to_url();
+        let parsed = parse_deep_link(&url).unwrap_or_else(|| panic!("failed to round-trip {}", url));
+        assert_eq!(parsed, action);
+    }
+
+    #[test]
+    fn test_round_trip_chat() {
+        round_trip(DeepLinkAction::Chat { user: "user 123".to_string() });
+    }
+
+    #[test]
+    fn test_round_trip_room() {
+        round_trip(DeepLinkAction::Room { id: "room-1".to_string() });
+    }
+
+    #[test]
+    fn test_round_trip_channel() {
+        round_trip(DeepLinkAction::Channel { id: "chan-1".to_string() });
+    }
+
+    #[test]
+    fn test_round_trip_server_without_channel() {
+        round_trip(DeepLinkAction::Server { id: "server-1".to_string(), channel: None });
+    }
+
+    #[test]
+    fn test_round_trip_server_with_channel() {
+        round_trip(DeepLinkAction::Server { id: "server-1".to_string(), channel: Some("chan-2".to_string()) });
+    }
+
+    #[test]
+    fn test_round_trip_invite() {
+        round_trip(DeepLinkAction::Invite {
+            code: "ABCD 1234".to_string(),
+            server: Some("server-1".to_string()),
+            ref_: Some("email campaign".to_string()),
+        });
+    }
+
+    #[test]
+    fn test_round_trip_invite_minimal() {
+        round_trip(DeepLinkAction::Invite { code: "ABCD1234".to_string(), server: None, ref_: None });
+    }
+
+    #[test]
+    fn test_round_trip_settings() {
+        round_trip(DeepLinkAction::Settings { section: Some("notifications".to_string()) });
+        round_trip(DeepLinkAction::Settings { section: None });
+    }
+
+    #[test]
+    fn test_round_trip_call() {
+        round_trip(DeepLinkAction::Call { id: "call-abc-123".to_string() });
     }
 }
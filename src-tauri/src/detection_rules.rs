@@ -0,0 +1,254 @@
+//! User-configurable app-detection rules for rich presence
+//!
+//! The process/window-title matching used to be a hardcoded `KNOWN_APPS`
+//! slice. This module loads a JSON file of detection rules from the app
+//! config dir, merges it over the built-in defaults below, and compiles
+//! each rule's pattern into a `Regex` once at load (substring rules are
+//! wrapped as an escaped, case-insensitive regex so both modes share one
+//! match path).
+
+use std::sync::Mutex;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, State};
+
+const RULES_FILE: &str = "detection_rules.json";
+
+/// Which field(s) a rule's pattern is tested against
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchOn {
+    ProcessName,
+    WindowTitle,
+    Either,
+}
+
+fn default_match_on() -> MatchOn {
+    MatchOn::Either
+}
+
+/// A single app-detection rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionRule {
+    /// Substring (default) or regex (when `regex` is true) to match
+    pub pattern: String,
+    /// Display name shown in rich presence
+    pub name: String,
+    /// Activity type (0=Playing, 1=Streaming, 2=Listening, 3=Watching)
+    pub activity_type: u8,
+    /// Treat `pattern` as a regex instead of a plain substring
+    #[serde(default)]
+    pub regex: bool,
+    /// Which field(s) to test `pattern` against
+    #[serde(default = "default_match_on")]
+    pub match_on: MatchOn,
+}
+
+/// The built-in defaults, matching what `KNOWN_APPS` used to hardcode
+const DEFAULT_RULES: &[(&str, &str, u8)] = &[
+    // Games (type 0 = Playing)
+    ("steam", "Steam", 0),
+    ("minecraft", "Minecraft", 0),
+    ("javaw", "Minecraft", 0),
+    ("league of legends", "League of Legends", 0),
+    ("leagueclient", "League of Legends", 0),
+    ("valorant", "VALORANT", 0),
+    ("csgo", "Counter-Strike", 0),
+    ("cs2", "Counter-Strike 2", 0),
+    ("dota2", "Dota 2", 0),
+    ("overwatch", "Overwatch", 0),
+    ("fortnite", "Fortnite", 0),
+    ("roblox", "Roblox", 0),
+    ("gta5", "GTA V", 0),
+    ("gtav", "GTA V", 0),
+    ("cyberpunk2077", "Cyberpunk 2077", 0),
+    ("eldenring", "Elden Ring", 0),
+    ("baldur", "Baldur's Gate 3", 0),
+    ("bg3", "Baldur's Gate 3", 0),
+    ("wow", "World of Warcraft", 0),
+    ("ffxiv", "Final Fantasy XIV", 0),
+    ("destiny2", "Destiny 2", 0),
+    ("apex", "Apex Legends", 0),
+    ("rust", "Rust", 0),
+    ("terraria", "Terraria", 0),
+    ("starcraft", "StarCraft", 0),
+    ("diablo", "Diablo", 0),
+    ("hearthstone", "Hearthstone", 0),
+    ("fallout", "Fallout", 0),
+    ("skyrim", "Skyrim", 0),
+    ("witcher", "The Witcher", 0),
+
+    // Music (type 2 = Listening)
+    ("spotify", "Spotify", 2),
+    ("music", "Apple Music", 2),
+    ("itunes", "iTunes", 2),
+    ("tidal", "Tidal", 2),
+    ("deezer", "Deezer", 2),
+    ("soundcloud", "SoundCloud", 2),
+    ("amazon music", "Amazon Music", 2),
+    ("vlc", "VLC", 2),
+    ("foobar", "foobar2000", 2),
+    ("musicbee", "MusicBee", 2),
+
+    // Video (type 3 = Watching)
+    ("netflix", "Netflix", 3),
+    ("plex", "Plex", 3),
+    ("mpv", "Video", 3),
+    ("kodi", "Kodi", 3),
+    ("obs", "OBS Studio", 1), // Streaming
+    ("streamlabs", "Streamlabs", 1),
+
+    // Development tools (type 0 = Playing/Using)
+    ("code", "Visual Studio Code", 0),
+    ("code - insiders", "VS Code Insiders", 0),
+    ("cursor", "Cursor", 0),
+    ("webstorm", "WebStorm", 0),
+    ("intellij", "IntelliJ IDEA", 0),
+    ("pycharm", "PyCharm", 0),
+    ("rider", "Rider", 0),
+    ("android studio", "Android Studio", 0),
+    ("xcode", "Xcode", 0),
+    ("sublime", "Sublime Text", 0),
+    ("atom", "Atom", 0),
+    ("vim", "Vim", 0),
+    ("nvim", "Neovim", 0),
+    ("emacs", "Emacs", 0),
+];
+
+fn default_rules() -> Vec<DetectionRule> {
+    DEFAULT_RULES
+        .iter()
+        .map(|(pattern, name, activity_type)| DetectionRule {
+            pattern: pattern.to_string(),
+            name: name.to_string(),
+            activity_type: *activity_type,
+            regex: false,
+            match_on: MatchOn::Either,
+        })
+        .collect()
+}
+
+/// A rule with its pattern pre-compiled into a `Regex`
+pub(crate) struct CompiledRule {
+    rule: DetectionRule,
+    regex: Regex,
+}
+
+fn compile(rule: DetectionRule) -> Option<CompiledRule> {
+    let pattern = if rule.regex {
+        rule.pattern.clone()
+    } else {
+        format!("(?i){}", regex::escape(&rule.pattern))
+    };
+
+    Regex::new(&pattern).ok().map(|regex| CompiledRule { rule, regex })
+}
+
+/// Managed state holding the currently active, compiled rule set
+pub type DetectionRulesState = Mutex<Vec<CompiledRule>>;
+
+fn rules_path<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(RULES_FILE))
+}
+
+/// User rules replace any default with a matching (case-insensitive)
+/// pattern, and are otherwise appended after the defaults
+fn merge_rules(defaults: Vec<DetectionRule>, overrides: Vec<DetectionRule>) -> Vec<DetectionRule> {
+    let mut merged = defaults;
+
+    for rule in overrides {
+        if let Some(existing) = merged.iter_mut().find(|r| r.pattern.eq_ignore_ascii_case(&rule.pattern)) {
+            *existing = rule;
+        } else {
+            merged.push(rule);
+        }
+    }
+
+    merged
+}
+
+fn load_from_disk<R: Runtime>(app: &AppHandle<R>) -> Vec<DetectionRule> {
+    let defaults = default_rules();
+
+    let Ok(path) = rules_path(app) else {
+        return defaults;
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return defaults;
+    };
+    let Ok(overrides) = serde_json::from_str::<Vec<DetectionRule>>(&contents) else {
+        return defaults;
+    };
+
+    merge_rules(defaults, overrides)
+}
+
+fn save_to_disk<R: Runtime>(app: &AppHandle<R>, rules: &[DetectionRule]) -> Result<(), String> {
+    let path = rules_path(app)?;
+    let contents = serde_json::to_string_pretty(rules).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Load detection rules from disk (or defaults) into managed state. Call
+/// once during `setup()`.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let compiled: Vec<CompiledRule> = load_from_disk(app).into_iter().filter_map(compile).collect();
+    app.manage(Mutex::new(compiled) as DetectionRulesState);
+}
+
+/// Match a process (and optionally its window title) against the active
+/// rule set, returning the display name and activity type of the first hit
+pub(crate) fn match_activity(
+    rules: &[CompiledRule],
+    process_name: &str,
+    window_title: Option<&str>,
+) -> Option<(String, u8)> {
+    for compiled in rules {
+        let check_process = matches!(compiled.rule.match_on, MatchOn::ProcessName | MatchOn::Either);
+        let check_title = matches!(compiled.rule.match_on, MatchOn::WindowTitle | MatchOn::Either);
+
+        if check_process && compiled.regex.is_match(process_name) {
+            return Some((compiled.rule.name.clone(), compiled.rule.activity_type));
+        }
+
+        if check_title {
+            if let Some(title) = window_title {
+                if compiled.regex.is_match(title) {
+                    return Some((compiled.rule.name.clone(), compiled.rule.activity_type));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Get the full current rule set
+#[tauri::command]
+pub fn get_detection_rules(state: State<DetectionRulesState>) -> Vec<DetectionRule> {
+    state.lock().unwrap().iter().map(|c| c.rule.clone()).collect()
+}
+
+/// Replace the rule set, recompile it, and persist it to disk. Fails
+/// without changing anything if any rule's pattern doesn't compile.
+#[tauri::command]
+pub fn set_detection_rules(
+    app: AppHandle,
+    state: State<DetectionRulesState>,
+    rules: Vec<DetectionRule>,
+) -> Result<(), String> {
+    let mut compiled = Vec::with_capacity(rules.len());
+    for rule in rules {
+        let pattern = rule.pattern.clone();
+        compiled.push(compile(rule).ok_or_else(|| format!("Invalid pattern: {}", pattern))?);
+    }
+
+    let rules: Vec<DetectionRule> = compiled.iter().map(|c| c.rule.clone()).collect();
+    save_to_disk(&app, &rules)?;
+    *state.lock().unwrap() = compiled;
+
+    Ok(())
+}
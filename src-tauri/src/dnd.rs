@@ -0,0 +1,243 @@
+//! Do-Not-Disturb: a quiet-hours schedule for `show_notification`, plus a
+//! replay queue so muting/focus mode/the schedule don't silently drop
+//! notifications
+//!
+//! `NOTIFICATIONS_MUTED` and `FOCUS_MODE_ACTIVE` (in `commands.rs`) already
+//! track the user's explicit mute/focus toggles; this adds the third
+//! suppression source (a daily time window) and the machinery to hold
+//! notifications raised while any of the three are active, then replay
+//! them once none are.
+
+use chrono::Timelike;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime, State};
+use tauri_plugin_notification::NotificationExt;
+
+const SCHEDULE_FILE: &str = "dnd_schedule.json";
+
+/// A daily quiet-hours window, e.g. 22:00-08:00 (wraps past midnight when
+/// `end` is earlier than `start`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DndSchedule {
+    pub enabled: bool,
+    /// "HH:MM", 24-hour, local time
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for DndSchedule {
+    fn default() -> Self {
+        Self { enabled: false, start: "22:00".to_string(), end: "08:00".to_string() }
+    }
+}
+
+/// A notification held back by mute/focus mode/the DND schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedNotification {
+    pub title: String,
+    pub body: String,
+}
+
+/// The active DND schedule
+pub type DndScheduleState = Mutex<DndSchedule>;
+
+/// Notifications suppressed while muted/focused/in quiet hours, awaiting replay
+pub type NotificationQueueState = Mutex<Vec<QueuedNotification>>;
+
+/// Whether the current DND state, along with the reason
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DndStatus {
+    pub active: bool,
+    pub muted: bool,
+    pub focus_mode: bool,
+    pub in_schedule: bool,
+    pub queued_count: usize,
+}
+
+fn schedule_path<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(SCHEDULE_FILE))
+}
+
+fn load_from_disk<R: Runtime>(app: &AppHandle<R>) -> DndSchedule {
+    let Ok(path) = schedule_path(app) else {
+        return DndSchedule::default();
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return DndSchedule::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+fn save_to_disk<R: Runtime>(app: &AppHandle<R>, schedule: &DndSchedule) -> Result<(), String> {
+    let path = schedule_path(app)?;
+    let contents = serde_json::to_string_pretty(schedule).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Load the DND schedule from disk (or defaults) into managed state, set
+/// up an empty notification queue, and start the schedule watcher. Call
+/// once during `setup()`.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    app.manage(Mutex::new(load_from_disk(app)) as DndScheduleState);
+    app.manage(Mutex::new(Vec::<QueuedNotification>::new()) as NotificationQueueState);
+    spawn_schedule_watcher(app.clone());
+}
+
+/// How often to recheck whether the DND schedule window has just ended
+const SCHEDULE_RECHECK_SECS: u64 = 60;
+
+/// Periodically recheck mute/focus mode/the DND schedule and replay the
+/// queue once they're all clear. `flush_if_clear` is otherwise only
+/// triggered by the mute/focus toggle handlers, so without this a quiet
+/// hours window ending on its own (e.g. 08:00 rolling around) would leave
+/// notifications queued until the user happens to toggle something.
+fn spawn_schedule_watcher<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(SCHEDULE_RECHECK_SECS)).await;
+            flush_if_clear(&app);
+        }
+    });
+}
+
+fn parse_minutes(time: &str) -> Option<u32> {
+    let (hours, minutes) = time.split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(hours * 60 + minutes)
+}
+
+fn minutes_since_midnight_local() -> u32 {
+    let now = chrono::Local::now();
+    now.hour() * 60 + now.minute()
+}
+
+/// Whether a notification raised right now would be held back by mute/focus
+/// mode/the DND schedule. Shared by `show_notification` and other call
+/// sites (e.g. screenshot capture) that want to respect the same rules
+/// without going through the `show_notification` command itself.
+pub(crate) fn is_suppressed<R: Runtime>(app: &AppHandle<R>) -> bool {
+    let muted = crate::commands::is_muted().unwrap_or(false);
+    let focused = crate::commands::is_focus_mode_active().unwrap_or(false);
+    let in_schedule = app
+        .try_state::<DndScheduleState>()
+        .map(|state| is_within_schedule(&state.lock().unwrap()))
+        .unwrap_or(false);
+    muted || focused || in_schedule
+}
+
+/// Whether "now" falls inside the configured DND schedule
+pub(crate) fn is_within_schedule(schedule: &DndSchedule) -> bool {
+    if !schedule.enabled {
+        return false;
+    }
+
+    let (Some(start), Some(end)) = (parse_minutes(&schedule.start), parse_minutes(&schedule.end)) else {
+        return false;
+    };
+    let now = minutes_since_midnight_local();
+
+    if start <= end {
+        now >= start && now < end
+    } else {
+        // Wraps past midnight, e.g. 22:00-08:00
+        now >= start || now < end
+    }
+}
+
+/// Hold a notification for replay once DND/mute/focus mode ends
+pub(crate) fn queue_notification(state: &NotificationQueueState, title: String, body: String) {
+    state.lock().unwrap().push(QueuedNotification { title, body });
+}
+
+/// Replace the DND schedule and persist it to disk
+#[tauri::command]
+pub fn set_dnd_schedule(app: AppHandle, state: State<DndScheduleState>, start: String, end: String) -> Result<(), String> {
+    if parse_minutes(&start).is_none() || parse_minutes(&end).is_none() {
+        return Err("start and end must be \"HH:MM\" in 24-hour time".to_string());
+    }
+
+    let schedule = DndSchedule { enabled: true, start, end };
+    save_to_disk(&app, &schedule)?;
+    *state.lock().unwrap() = schedule;
+    Ok(())
+}
+
+/// Disable the DND schedule (mute/focus mode still suppress independently)
+#[tauri::command]
+pub fn clear_dnd_schedule(app: AppHandle, state: State<DndScheduleState>) -> Result<(), String> {
+    let mut schedule = state.lock().unwrap();
+    schedule.enabled = false;
+    save_to_disk(&app, &schedule)?;
+    Ok(())
+}
+
+/// The current DND state and why it's active, if it is
+#[tauri::command]
+pub fn get_dnd_status(
+    schedule_state: State<DndScheduleState>,
+    queue_state: State<NotificationQueueState>,
+) -> DndStatus {
+    let muted = crate::commands::is_muted().unwrap_or(false);
+    let focus_mode = crate::commands::is_focus_mode_active().unwrap_or(false);
+    let in_schedule = is_within_schedule(&schedule_state.lock().unwrap());
+
+    DndStatus {
+        active: muted || focus_mode || in_schedule,
+        muted,
+        focus_mode,
+        in_schedule,
+        queued_count: queue_state.lock().unwrap().len(),
+    }
+}
+
+/// Replay every queued notification and clear the queue. Safe to call even
+/// when DND is still active — callers are expected to check
+/// `get_dnd_status` first.
+#[tauri::command]
+pub fn flush_notification_queue(app: AppHandle, state: State<NotificationQueueState>) -> Result<Vec<QueuedNotification>, String> {
+    let queued = std::mem::take(&mut *state.lock().unwrap());
+
+    for notification in &queued {
+        app.notification()
+            .builder()
+            .title(&notification.title)
+            .body(&notification.body)
+            .show()
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(queued)
+}
+
+/// Replay the notification queue if mute/focus mode/the DND schedule are
+/// all now clear. A no-op otherwise. Called by whichever code path just
+/// turned mute or focus mode off, since that's the one moment we know to
+/// recheck without polling.
+pub(crate) fn flush_if_clear<R: Runtime>(app: &AppHandle<R>) {
+    let muted = crate::commands::is_muted().unwrap_or(false);
+    let focused = crate::commands::is_focus_mode_active().unwrap_or(false);
+    let in_schedule = app
+        .try_state::<DndScheduleState>()
+        .map(|state| is_within_schedule(&state.lock().unwrap()))
+        .unwrap_or(false);
+
+    if muted || focused || in_schedule {
+        return;
+    }
+
+    let Some(queue_state) = app.try_state::<NotificationQueueState>() else {
+        return;
+    };
+    let queued = std::mem::take(&mut *queue_state.lock().unwrap());
+
+    for notification in queued {
+        let _ = app.notification().builder().title(&notification.title).body(&notification.body).show();
+    }
+}
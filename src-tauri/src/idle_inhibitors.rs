@@ -0,0 +1,260 @@
+//! Configurable idle inhibitors for "user present" detection
+//!
+//! Raw input idle time alone flags a user idle after 5 minutes even while
+//! they're watching a movie or waiting on a long build. This tracks a few
+//! additional signals — active audio playback, a fullscreen foreground app,
+//! and optionally a CPU-load floor — any of which can hold the session
+//! "active" regardless of input idle time. Each signal is independently
+//! toggleable via `set_idle_inhibitor_config`.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use sysinfo::System;
+use tauri::{AppHandle, Manager, Runtime, State};
+
+const CONFIG_FILE: &str = "idle_inhibitors.json";
+
+/// Which signals can inhibit idle, and at what sensitivity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdleInhibitorConfig {
+    /// Treat active audio playback as "present"
+    pub audio_playback: bool,
+    /// Treat a fullscreen foreground app as "present"
+    pub fullscreen_app: bool,
+    /// Treat system-wide CPU usage above this percentage as "present".
+    /// `None` disables the CPU-load signal.
+    pub cpu_load_floor: Option<f32>,
+}
+
+impl Default for IdleInhibitorConfig {
+    fn default() -> Self {
+        Self { audio_playback: true, fullscreen_app: true, cpu_load_floor: None }
+    }
+}
+
+/// Managed state holding the active inhibitor config
+pub type IdleInhibitorConfigState = Mutex<IdleInhibitorConfig>;
+
+/// The cached `sysinfo::System` used for the CPU-load inhibitor, refreshed
+/// on each check rather than rebuilt from scratch
+static CPU_SYSTEM: Mutex<Option<System>> = Mutex::new(None);
+
+fn config_path<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(CONFIG_FILE))
+}
+
+fn load_from_disk<R: Runtime>(app: &AppHandle<R>) -> IdleInhibitorConfig {
+    let Ok(path) = config_path(app) else {
+        return IdleInhibitorConfig::default();
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return IdleInhibitorConfig::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+fn save_to_disk<R: Runtime>(app: &AppHandle<R>, config: &IdleInhibitorConfig) -> Result<(), String> {
+    let path = config_path(app)?;
+    let contents = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Load the inhibitor config from disk (or defaults) into managed state.
+/// Call once during `setup()`.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    app.manage(Mutex::new(load_from_disk(app)) as IdleInhibitorConfigState);
+}
+
+/// Get the active inhibitor config
+#[tauri::command]
+pub fn get_idle_inhibitor_config(state: State<IdleInhibitorConfigState>) -> IdleInhibitorConfig {
+    state.lock().unwrap().clone()
+}
+
+/// Replace the inhibitor config and persist it to disk
+#[tauri::command]
+pub fn set_idle_inhibitor_config(
+    app: AppHandle,
+    state: State<IdleInhibitorConfigState>,
+    config: IdleInhibitorConfig,
+) -> Result<(), String> {
+    save_to_disk(&app, &config)?;
+    *state.lock().unwrap() = config;
+    Ok(())
+}
+
+/// Names of every currently-active inhibitor, given the active config.
+/// Empty means nothing is holding the session present.
+pub(crate) fn active_inhibitors(config: &IdleInhibitorConfig) -> Vec<String> {
+    let mut inhibitors = Vec::new();
+
+    if config.audio_playback && audio_playback_active() {
+        inhibitors.push("audio_playback".to_string());
+    }
+
+    if config.fullscreen_app && fullscreen_foreground_app() {
+        inhibitors.push("fullscreen_app".to_string());
+    }
+
+    if let Some(floor) = config.cpu_load_floor {
+        if cpu_usage_percent() > floor {
+            inhibitors.push("cpu_load".to_string());
+        }
+    }
+
+    inhibitors
+}
+
+fn cpu_usage_percent() -> f32 {
+    let mut guard = CPU_SYSTEM.lock().unwrap();
+    let system = guard.get_or_insert_with(System::new_all);
+    system.refresh_cpu_usage();
+    system.global_cpu_usage()
+}
+
+#[cfg(target_os = "linux")]
+fn audio_playback_active() -> bool {
+    use std::process::Command;
+
+    // pipewire-pulse and PulseAudio proper both answer to `pactl`
+    let Ok(output) = Command::new("pactl").args(["list", "sink-inputs", "short"]).output() else {
+        return false;
+    };
+
+    !output.stdout.is_empty()
+}
+
+#[cfg(target_os = "windows")]
+fn audio_playback_active() -> bool {
+    use windows::Media::Control::{GlobalSystemMediaTransportControlsSessionManager, GlobalSystemMediaTransportControlsSessionPlaybackStatus};
+
+    let Ok(manager) = GlobalSystemMediaTransportControlsSessionManager::RequestAsync() else {
+        return false;
+    };
+    let Ok(manager) = manager.get() else {
+        return false;
+    };
+    let Ok(session) = manager.GetCurrentSession() else {
+        return false;
+    };
+    let Ok(info) = session.GetPlaybackInfo() else {
+        return false;
+    };
+    let Ok(status) = info.PlaybackStatus() else {
+        return false;
+    };
+
+    status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing
+}
+
+#[cfg(target_os = "macos")]
+fn audio_playback_active() -> bool {
+    // No public CoreAudio API reports "is anything playing"; a player in
+    // the playing state (same signal `media::now_playing` already derives
+    // via AppleScript) is a good enough proxy
+    crate::media::now_playing().is_some()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn audio_playback_active() -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn fullscreen_foreground_app() -> bool {
+    use std::process::Command;
+
+    let Ok(active) = Command::new("xdotool").args(["getactivewindow"]).output() else {
+        return false;
+    };
+    let Ok(window_id) = String::from_utf8_lossy(&active.stdout).trim().parse::<String>() else {
+        return false;
+    };
+
+    let Ok(geometry) = Command::new("xdotool").args(["getwindowgeometry", "--shell", &window_id]).output() else {
+        return false;
+    };
+    let geometry = String::from_utf8_lossy(&geometry.stdout);
+    let window_w = shell_var(&geometry, "WIDTH");
+    let window_h = shell_var(&geometry, "HEIGHT");
+
+    let Ok(screen) = Command::new("xdotool").args(["getdisplaygeometry"]).output() else {
+        return false;
+    };
+    let screen = String::from_utf8_lossy(&screen.stdout);
+    let mut parts = screen.split_whitespace();
+    let screen_w = parts.next().and_then(|s| s.parse::<i64>().ok());
+    let screen_h = parts.next().and_then(|s| s.parse::<i64>().ok());
+
+    matches!((window_w, window_h, screen_w, screen_h), (Some(ww), Some(wh), Some(sw), Some(sh)) if ww >= sw && wh >= sh)
+}
+
+#[cfg(target_os = "linux")]
+fn shell_var(output: &str, key: &str) -> Option<i64> {
+    output.lines().find_map(|line| line.strip_prefix(&format!("{key}="))?.parse().ok())
+}
+
+#[cfg(target_os = "macos")]
+fn fullscreen_foreground_app() -> bool {
+    use std::process::Command;
+
+    let script = r#"tell application "System Events"
+        set frontApp to name of first application process whose frontmost is true
+        tell process frontApp
+            try
+                return value of attribute "AXFullScreen" of front window
+            on error
+                return false
+            end try
+        end tell
+    end tell"#;
+
+    let Ok(output) = Command::new("osascript").args(["-e", script]).output() else {
+        return false;
+    };
+
+    String::from_utf8_lossy(&output.stdout).trim() == "true"
+}
+
+#[cfg(target_os = "windows")]
+fn fullscreen_foreground_app() -> bool {
+    use std::process::Command;
+
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            r#"
+            Add-Type @'
+            using System;
+            using System.Runtime.InteropServices;
+            public class FullscreenCheck {
+                [DllImport("user32.dll")] static extern IntPtr GetForegroundWindow();
+                [DllImport("user32.dll")] static extern bool GetWindowRect(IntPtr hWnd, out RECT rect);
+                [DllImport("user32.dll")] static extern int GetSystemMetrics(int index);
+                [StructLayout(LayoutKind.Sequential)]
+                public struct RECT { public int Left, Top, Right, Bottom; }
+                public static bool IsFullscreen() {
+                    RECT rect;
+                    var hwnd = GetForegroundWindow();
+                    if (!GetWindowRect(hwnd, out rect)) return false;
+                    int screenW = GetSystemMetrics(0);
+                    int screenH = GetSystemMetrics(1);
+                    return (rect.Right - rect.Left) >= screenW && (rect.Bottom - rect.Top) >= screenH;
+                }
+            }
+'@
+            [FullscreenCheck]::IsFullscreen()
+            "#,
+        ])
+        .output();
+
+    matches!(output, Ok(output) if String::from_utf8_lossy(&output.stdout).trim().eq_ignore_ascii_case("true"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn fullscreen_foreground_app() -> bool {
+    false
+}
@@ -0,0 +1,133 @@
+//! User-configurable keybindings for the app menu
+//!
+//! Every accelerator in `create_menu` used to be a string literal baked
+//! into the `MenuItem::with_id` call. This module loads an action-id ->
+//! accelerator map from a JSON file in the app config dir, falling back to
+//! the baked-in defaults below, and `create_menu` consults it instead of
+//! hardcoding accelerators. A binding can be cleared entirely by mapping
+//! an action to `null`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::menu::MenuItem;
+use tauri::{AppHandle, Manager, Runtime};
+
+const KEYMAP_FILE: &str = "keymap.json";
+
+/// The built-in defaults, matching what `create_menu` used to hardcode
+const DEFAULT_KEYMAP: &[(&str, &str)] = &[
+    ("new_chat", "CommandOrControl+N"),
+    ("new_room", "CommandOrControl+Shift+N"),
+    ("settings", "CommandOrControl+,"),
+    ("toggle_sidebar", "CommandOrControl+\\"),
+    ("zoom_in", "CommandOrControl+Plus"),
+    ("zoom_out", "CommandOrControl+-"),
+    ("zoom_reset", "CommandOrControl+0"),
+    ("toggle_fullscreen", "F11"),
+    ("reload", "CommandOrControl+R"),
+    ("dev_tools", "CommandOrControl+Shift+I"),
+];
+
+fn default_map() -> HashMap<String, Option<String>> {
+    DEFAULT_KEYMAP
+        .iter()
+        .map(|(id, accel)| (id.to_string(), Some(accel.to_string())))
+        .collect()
+}
+
+/// Managed state holding the currently active keymap
+pub type KeymapState = Mutex<HashMap<String, Option<String>>>;
+
+fn keymap_path<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(KEYMAP_FILE))
+}
+
+fn load_from_disk<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, Option<String>> {
+    let mut map = default_map();
+
+    if let Ok(path) = keymap_path(app) {
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            if let Ok(overrides) = serde_json::from_str::<HashMap<String, Option<String>>>(&contents) {
+                for (id, accelerator) in overrides {
+                    map.insert(id, accelerator);
+                }
+            }
+        }
+    }
+
+    map
+}
+
+fn save_to_disk<R: Runtime>(
+    app: &AppHandle<R>,
+    map: &HashMap<String, Option<String>>,
+) -> Result<(), String> {
+    let path = keymap_path(app)?;
+    let contents = serde_json::to_string_pretty(map).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Load the keymap from disk (or defaults) into managed state. Call once
+/// during `setup()`.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let map = load_from_disk(app);
+    app.manage(Mutex::new(map) as KeymapState);
+}
+
+/// Look up the accelerator currently bound to an action, falling back to
+/// the baked-in default if managed state isn't available (i.e. `init`
+/// hasn't run yet).
+pub fn accelerator_for<R: Runtime>(app: &AppHandle<R>, action_id: &str) -> Option<String> {
+    if let Some(state) = app.try_state::<KeymapState>() {
+        return state.lock().unwrap().get(action_id).cloned().flatten();
+    }
+
+    DEFAULT_KEYMAP
+        .iter()
+        .find(|(id, _)| *id == action_id)
+        .map(|(_, accel)| accel.to_string())
+}
+
+/// Get the full current keymap
+#[tauri::command]
+pub fn get_keymap(state: tauri::State<KeymapState>) -> HashMap<String, Option<String>> {
+    state.lock().unwrap().clone()
+}
+
+/// Rebind (or clear, by passing `None`) a single action, persist it, and
+/// rebuild the app menu so the change takes effect immediately
+#[tauri::command]
+pub fn set_keybinding(
+    app: AppHandle,
+    state: tauri::State<KeymapState>,
+    action_id: String,
+    accelerator: Option<String>,
+) -> Result<(), String> {
+    // Validate before persisting: an unparseable accelerator would only
+    // surface as a `create_menu` error on the *next* launch, by which
+    // point it's already on disk and the app can't start
+    if let Some(accel) = &accelerator {
+        MenuItem::with_id(&app, "__keymap_validate", "", true, Some(accel.as_str()))
+            .map_err(|e| format!("Invalid accelerator \"{}\": {}", accel, e))?;
+    }
+
+    {
+        let mut map = state.lock().unwrap();
+        map.insert(action_id, accelerator);
+        save_to_disk(&app, &map)?;
+    }
+    crate::menu::rebuild_menu(&app).map_err(|e| e.to_string())
+}
+
+/// Reset every binding back to the built-in defaults
+#[tauri::command]
+pub fn reset_keymap(app: AppHandle, state: tauri::State<KeymapState>) -> Result<(), String> {
+    {
+        let mut map = state.lock().unwrap();
+        *map = default_map();
+        save_to_disk(&app, &map)?;
+    }
+    crate::menu::rebuild_menu(&app).map_err(|e| e.to_string())
+}
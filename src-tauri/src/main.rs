@@ -3,13 +3,25 @@
 
 mod activity;
 mod audio;
+mod call_audio;
+mod command_palette;
 mod commands;
+mod context_menu;
 mod deeplink;
+mod detection_rules;
+mod dnd;
+mod idle_inhibitors;
+mod keymap;
+mod media;
 mod menu;
+mod menu_registry;
 mod power;
 mod screenshot;
+mod shortcuts;
+mod steam;
 mod tray;
 mod updater;
+mod window_state;
 
 use tauri::{GlobalShortcutBuilder, Manager, WindowEvent};
 use tauri_plugin_window_state::{AppHandleExt, StateFlags};
@@ -26,6 +38,12 @@ fn main() {
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_window_state::Builder::new().build())
+        .manage(menu_registry::MenuRegistryState::default())
+        .manage(audio::InputMonitorState::default())
+        .manage(call_audio::CallAudioState::default())
+        .manage(activity::ActivityMonitorState::default())
+        .manage(screenshot::LastScreenshotState::default())
+        .manage(power::PowerMonitorState::default())
         .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
             // When a second instance is launched, focus the existing window
             let _ = app
@@ -41,17 +59,40 @@ fn main() {
             }
         }))
         .on_window_event(|window, event| {
-            // Minimize to tray on close instead of quitting
-            if let WindowEvent::CloseRequested { api, .. } = event {
-                // Save window state before hiding
-                let _ = window.app_handle().save_window_state(StateFlags::all());
-                // Hide the window instead of closing
-                let _ = window.hide();
-                // Prevent the window from being destroyed
-                api.prevent_close();
+            match event {
+                WindowEvent::CloseRequested { api, .. } => {
+                    // Save window state before hiding
+                    let _ = window.app_handle().save_window_state(StateFlags::all());
+                    // Hide the window instead of closing
+                    let _ = window.hide();
+                    // Prevent the window from being destroyed
+                    api.prevent_close();
+                }
+                WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+                    let _ = window.app_handle().save_window_state(StateFlags::all());
+                }
+                _ => {}
             }
         })
         .setup(|app| {
+            // Load the user's keybindings (or defaults) before any menu is built
+            keymap::init(app.handle());
+
+            // Load the user's app-detection rules (or defaults)
+            detection_rules::init(app.handle());
+
+            // Load the DND schedule (or defaults) and set up the notification replay queue
+            dnd::init(app.handle());
+
+            // Build the Steam library index for accurate game names/art
+            steam::init(app.handle());
+
+            // Load the user's idle-inhibitor config (or defaults)
+            idle_inhibitors::init(app.handle());
+
+            // Watch for audio devices being plugged/unplugged
+            audio::spawn_device_watcher(app.handle().clone());
+
             // Set up system tray
             tray::setup_tray(app)?;
 
@@ -65,85 +106,19 @@ fn main() {
             #[cfg(target_os = "macos")]
             window.set_decorations(true)?;
 
-            // Register global shortcuts
-            let shortcut_manager = app.global_shortcut_manager();
-
-            // Toggle window visibility with Cmd/Ctrl+Shift+H
-            shortcut_manager
-                .register("CommandOrControl+Shift+H", {
-                    let app_handle = app.handle().clone();
-                    move || {
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            if window.is_visible().unwrap_or(false) {
-                                let _ = window.hide();
-                            } else {
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                        }
-                    }
-                })
-                .ok();
-
-            // Show window with Cmd/Ctrl+Shift+S
-            shortcut_manager
-                .register("CommandOrControl+Shift+S", {
-                    let app_handle = app.handle().clone();
-                    move || {
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                })
-                .ok();
+            // Load the user's shortcut bindings (or defaults) and register
+            // them with global_shortcut_manager. Replaces the hardcoded
+            // Cmd/Ctrl+Shift+H/S/M/F registrations that used to live here.
+            shortcuts::init(app.handle());
 
-            // Toggle mute with Cmd/Ctrl+Shift+M
-            shortcut_manager
-                .register("CommandOrControl+Shift+M", {
-                    let app_handle = app.handle().clone();
-                    move || {
-                        let muted = crate::commands::toggle_mute().unwrap_or(false);
-                        // Update the tray menu to reflect new state
-                        let _ = crate::tray::update_tray_mute_state(&app_handle, muted);
-                        
-                        // Show a toast notification via the window
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            let message = if muted {
-                                "Notifications muted"
-                            } else {
-                                "Notifications unmuted"
-                            };
-                            let _ = window.emit("mute-state-changed", serde_json::json!({
-                                "muted": muted,
-                                "message": message
-                            }));
-                        }
-                    }
-                })
-                .ok();
+            let shortcut_manager = app.global_shortcut_manager();
 
-            // Toggle focus mode with Cmd/Ctrl+Shift+F
+            // Open the command palette with Cmd/Ctrl+Shift+P
             shortcut_manager
-                .register("CommandOrControl+Shift+F", {
+                .register("CommandOrControl+Shift+P", {
                     let app_handle = app.handle().clone();
                     move || {
-                        let active = crate::commands::toggle_focus_mode().unwrap_or(false);
-                        // Update the tray menu to reflect new state
-                        let _ = crate::tray::update_tray_focus_state(&app_handle, active);
-                        
-                        // Show a toast notification via the window
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            let message = if active {
-                                "Focus mode enabled - only mentions and DMs"
-                            } else {
-                                "Focus mode disabled"
-                            };
-                            let _ = window.emit("focus-mode-changed", serde_json::json!({
-                                "active": active,
-                                "message": message
-                            }));
-                        }
+                        command_palette::show_command_palette(&app_handle);
                     }
                 })
                 .ok();
@@ -167,10 +142,18 @@ fn main() {
                 updater::check_updates_on_startup(update_handle).await;
             });
 
+            // Keep re-checking in the background, and let any part of the
+            // app force an immediate check via the "updater://check-now" event
+            updater::spawn_periodic_update_check(app.handle().clone());
+            updater::register_check_now_listener(app.handle());
+
             Ok(())
         })
         .on_menu_event(|app, event| {
-            menu::handle_menu_event(app, event.id().as_ref());
+            let action = event.id().as_ref();
+            if !context_menu::handle_menu_event(app, action) {
+                menu::handle_menu_event(app, action);
+            }
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_app_version,
@@ -191,6 +174,8 @@ fn main() {
             commands::clipboard_read_text,
             commands::clipboard_has_text,
             commands::clipboard_clear,
+            commands::clipboard_write_image,
+            commands::clipboard_read_image,
             // Quick Mute commands
             commands::toggle_mute,
             commands::is_muted,
@@ -198,6 +183,23 @@ fn main() {
             // Tray badge commands
             commands::update_tray_badge,
             commands::get_tray_badge,
+            commands::set_badges_enabled,
+            commands::are_badges_enabled,
+            // Menu registry commands
+            menu_registry::menu_set_enabled,
+            menu_registry::menu_set_text,
+            menu_registry::menu_set_checked,
+            // Context menu commands
+            context_menu::show_context_menu,
+            // Command palette commands
+            command_palette::list_commands,
+            command_palette::invoke_command,
+            // Keymap commands
+            keymap::get_keymap,
+            keymap::set_keybinding,
+            keymap::reset_keymap,
+            // Deep link commands
+            deeplink::build_deep_link,
             // Focus Mode commands
             commands::toggle_focus_mode,
             commands::is_focus_mode_active,
@@ -208,24 +210,53 @@ fn main() {
             commands::file_exists,
             commands::get_file_info,
             updater::check_for_updates,
+            updater::recheck_for_updates,
+            updater::skip_update_version,
+            updater::defer_update,
             updater::download_and_install_update,
+            updater::get_low_battery_threshold,
+            updater::set_low_battery_threshold,
             // Activity detection for rich presence
             activity::get_running_activities,
             activity::get_idle_status,
             activity::get_idle_status_with_threshold,
+            activity::start_activity_monitor,
+            activity::stop_activity_monitor,
+            idle_inhibitors::get_idle_inhibitor_config,
+            idle_inhibitors::set_idle_inhibitor_config,
+            // App-detection rules
+            detection_rules::get_detection_rules,
+            detection_rules::set_detection_rules,
+            // Do-Not-Disturb schedule and notification replay queue
+            dnd::set_dnd_schedule,
+            dnd::clear_dnd_schedule,
+            dnd::get_dnd_status,
+            dnd::flush_notification_queue,
+            // Global shortcut bindings
+            shortcuts::get_shortcut_bindings,
+            shortcuts::set_shortcut_binding,
+            shortcuts::reset_shortcut_bindings,
+            // Steam library integration
+            steam::refresh_steam_library,
             // Power management commands
             power::prevent_sleep,
             power::allow_sleep,
             power::is_sleep_prevented,
             power::get_power_status,
+            power::start_power_monitoring,
+            power::stop_power_monitoring,
             // Screenshot commands
             screenshot::capture_screenshot,
+            screenshot::capture_screenshot_to_clipboard,
             screenshot::capture_window_screenshot,
             screenshot::capture_region_screenshot,
             screenshot::get_screenshots_dir,
             screenshot::list_screenshots,
             screenshot::delete_screenshot,
+            screenshot::last_screenshot,
             // Audio commands
+            audio::list_audio_hosts,
+            audio::set_audio_host,
             audio::get_audio_input_devices,
             audio::get_audio_output_devices,
             audio::set_audio_input_device,
@@ -236,6 +267,17 @@ fn main() {
             audio::set_output_volume,
             audio::is_output_muted,
             audio::toggle_output_mute,
+            audio::start_input_monitor,
+            audio::stop_input_monitor,
+            // Call audio routing (jitter-buffered playback)
+            call_audio::get_audio_buffering,
+            call_audio::set_audio_buffering,
+            call_audio::start_call_audio,
+            call_audio::stop_call_audio,
+            call_audio::push_call_audio,
+            // Window-state persistence
+            window_state::save_window_state,
+            window_state::restore_window_state,
         ])
         .run(tauri::generate_context!())
         .expect("error while running Hearth desktop application");
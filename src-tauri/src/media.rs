@@ -0,0 +1,172 @@
+//! Now-playing media metadata for rich presence
+//!
+//! Resolves the current track from whatever the OS considers "now playing",
+//! independent of which specific player process we matched in `activity.rs`.
+
+use serde::{Deserialize, Serialize};
+
+/// Now-playing metadata resolved from the OS media session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MediaMetadata {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub position_ms: Option<u64>,
+    pub duration_ms: Option<u64>,
+    /// A URL (MPRIS `artUrl`) or cached file path to the album art
+    pub artwork: Option<String>,
+}
+
+/// Resolve the current now-playing metadata, if any track is active
+pub fn now_playing() -> Option<MediaMetadata> {
+    #[cfg(target_os = "linux")]
+    {
+        linux_mpris_now_playing()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        windows_smtc_now_playing()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos_now_playing()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn linux_mpris_now_playing() -> Option<MediaMetadata> {
+    use dbus::arg::{RefArg, Variant};
+    use dbus::blocking::Connection;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    let conn = Connection::new_session().ok()?;
+    const TIMEOUT: Duration = Duration::from_millis(500);
+
+    // Any running MPRIS player registers a org.mpris.MediaPlayer2.* name;
+    // just take the first one with playable metadata
+    let bus_proxy = conn.with_proxy("org.freedesktop.DBus", "/org/freedesktop/DBus", TIMEOUT);
+    let (names,): (Vec<String>,) = bus_proxy
+        .method_call("org.freedesktop.DBus", "ListNames", ())
+        .ok()?;
+    let (player, metadata) = names
+        .into_iter()
+        .filter(|name| name.starts_with("org.mpris.MediaPlayer2."))
+        .find_map(|name| {
+            let player = conn.with_proxy(name, "/org/mpris/MediaPlayer2", TIMEOUT);
+            let metadata: HashMap<String, Variant<Box<dyn RefArg>>> = player
+                .get("org.mpris.MediaPlayer2.Player", "Metadata")
+                .ok()?;
+            (!metadata.is_empty()).then_some((player, metadata))
+        })?;
+
+    let title = metadata
+        .get("xesam:title")
+        .and_then(|v| v.0.as_str())
+        .map(|s| s.to_string());
+    let artist = metadata
+        .get("xesam:artist")
+        .and_then(|v| v.0.as_iter())
+        .and_then(|mut iter| iter.next())
+        .and_then(|a| a.as_str().map(|s| s.to_string()));
+    let album = metadata
+        .get("xesam:album")
+        .and_then(|v| v.0.as_str())
+        .map(|s| s.to_string());
+    let artwork = metadata
+        .get("mpris:artUrl")
+        .and_then(|v| v.0.as_str())
+        .map(|s| s.to_string());
+    let duration_ms = metadata
+        .get("mpris:length")
+        .and_then(|v| v.0.as_i64())
+        .map(|micros| (micros / 1000) as u64);
+
+    let position_ms = player
+        .get::<i64>("org.mpris.MediaPlayer2.Player", "Position")
+        .ok()
+        .map(|micros| (micros / 1000) as u64);
+
+    Some(MediaMetadata { title, artist, album, position_ms, duration_ms, artwork })
+}
+
+#[cfg(target_os = "windows")]
+fn windows_smtc_now_playing() -> Option<MediaMetadata> {
+    use windows::Media::Control::GlobalSystemMediaTransportControlsSessionManager;
+
+    let manager = GlobalSystemMediaTransportControlsSessionManager::RequestAsync()
+        .ok()?
+        .get()
+        .ok()?;
+    let session = manager.GetCurrentSession().ok()?;
+
+    let props = session.TryGetMediaPropertiesAsync().ok()?.get().ok()?;
+    let title = props.Title().ok().map(|s| s.to_string_lossy());
+    let artist = props.Artist().ok().map(|s| s.to_string_lossy());
+    let album = props.AlbumTitle().ok().map(|s| s.to_string_lossy());
+
+    let timeline = session.GetTimelineProperties().ok();
+    let position_ms = timeline
+        .as_ref()
+        .and_then(|t| t.Position().ok())
+        .map(|d| (d.Duration / 10_000).max(0) as u64);
+    let duration_ms = timeline
+        .as_ref()
+        .and_then(|t| t.EndTime().ok())
+        .map(|d| (d.Duration / 10_000).max(0) as u64);
+
+    Some(MediaMetadata { title, artist, album, position_ms, duration_ms, artwork: None })
+}
+
+#[cfg(target_os = "macos")]
+fn macos_now_playing() -> Option<MediaMetadata> {
+    // MediaRemote is a private framework with no public header; ask each
+    // known player directly over AppleScript instead
+    ["Spotify", "Music"]
+        .into_iter()
+        .find_map(macos_player_now_playing)
+}
+
+#[cfg(target_os = "macos")]
+fn macos_player_now_playing(app: &str) -> Option<MediaMetadata> {
+    use std::process::Command;
+
+    let script = format!(
+        r#"if application "{app}" is running then
+            tell application "{app}"
+                if player state is playing then
+                    return (name of current track) & "||" & (artist of current track) & "||" & (album of current track) & "||" & (player position as string) & "||" & (duration of current track as string)
+                end if
+            end tell
+        end if
+        return """#,
+        app = app
+    );
+
+    let output = Command::new("osascript").args(["-e", &script]).output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+
+    let parts: Vec<&str> = text.split("||").collect();
+    if parts.len() < 5 {
+        return None;
+    }
+
+    Some(MediaMetadata {
+        title: Some(parts[0].to_string()),
+        artist: Some(parts[1].to_string()),
+        album: Some(parts[2].to_string()),
+        position_ms: parts[3].parse::<f64>().ok().map(|secs| (secs * 1000.0) as u64),
+        duration_ms: parts[4].parse::<f64>().ok().map(|secs| (secs * 1000.0) as u64),
+        artwork: None,
+    })
+}
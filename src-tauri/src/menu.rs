@@ -1,25 +1,45 @@
 use tauri::{
-    menu::{AboutMetadata, Menu, MenuItem, PredefinedMenuItem, Submenu},
+    menu::{AboutMetadata, CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     AppHandle, Manager, Runtime, Wry,
 };
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::keymap;
+use crate::menu_registry::MenuRegistryState;
+
+/// Shorthand for looking up a keymap-configurable accelerator
+fn accel(app: &AppHandle<Wry>, action_id: &str) -> Option<String> {
+    keymap::accelerator_for(app, action_id)
+}
 
 pub fn create_menu(app: &AppHandle<Wry>) -> Result<Menu<Wry>, Box<dyn std::error::Error>> {
+    let new_chat_i = MenuItem::with_id(app, "new_chat", "New Chat", true, accel(app, "new_chat").as_deref())?;
+    let new_room_i = MenuItem::with_id(app, "new_room", "New Room", true, accel(app, "new_room").as_deref())?;
+    let settings_i = MenuItem::with_id(app, "settings", "Settings...", true, accel(app, "settings").as_deref())?;
+
     // File menu
     let file_menu = Submenu::with_items(
         app,
         "File",
         true,
         &[
-            &MenuItem::with_id(app, "new_chat", "New Chat", true, Some("CommandOrControl+N"))?,
-            &MenuItem::with_id(app, "new_room", "New Room", true, Some("CommandOrControl+Shift+N"))?,
+            &new_chat_i,
+            &new_room_i,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "settings", "Settings...", true, Some("CommandOrControl+,"))?,
+            &settings_i,
             &PredefinedMenuItem::separator(app)?,
             #[cfg(not(target_os = "macos"))]
             &PredefinedMenuItem::quit(app, Some("Quit"))?,
         ],
     )?;
 
+    if let Some(registry) = app.try_state::<MenuRegistryState>() {
+        let mut registry = registry.lock().unwrap();
+        registry.register("new_chat", new_chat_i);
+        registry.register("new_room", new_room_i);
+        registry.register("settings", settings_i);
+    }
+
     // Edit menu
     let edit_menu = Submenu::with_items(
         app,
@@ -42,19 +62,48 @@ pub fn create_menu(app: &AppHandle<Wry>) -> Result<Menu<Wry>, Box<dyn std::error
         "View",
         true,
         &[
-            &MenuItem::with_id(app, "toggle_sidebar", "Toggle Sidebar", true, Some("CommandOrControl+\\"))?,
+            &MenuItem::with_id(app, "toggle_sidebar", "Toggle Sidebar", true, accel(app, "toggle_sidebar").as_deref())?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "zoom_in", "Zoom In", true, Some("CommandOrControl+Plus"))?,
-            &MenuItem::with_id(app, "zoom_out", "Zoom Out", true, Some("CommandOrControl+-"))?,
-            &MenuItem::with_id(app, "zoom_reset", "Actual Size", true, Some("CommandOrControl+0"))?,
+            &MenuItem::with_id(app, "zoom_in", "Zoom In", true, accel(app, "zoom_in").as_deref())?,
+            &MenuItem::with_id(app, "zoom_out", "Zoom Out", true, accel(app, "zoom_out").as_deref())?,
+            &MenuItem::with_id(app, "zoom_reset", "Actual Size", true, accel(app, "zoom_reset").as_deref())?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "toggle_fullscreen", "Toggle Full Screen", true, Some("F11"))?,
-            &MenuItem::with_id(app, "reload", "Reload", true, Some("CommandOrControl+R"))?,
+            &MenuItem::with_id(app, "toggle_fullscreen", "Toggle Full Screen", true, accel(app, "toggle_fullscreen").as_deref())?,
+            &MenuItem::with_id(app, "reload", "Reload", true, accel(app, "reload").as_deref())?,
             #[cfg(debug_assertions)]
-            &MenuItem::with_id(app, "dev_tools", "Developer Tools", true, Some("CommandOrControl+Shift+I"))?,
+            &MenuItem::with_id(app, "dev_tools", "Developer Tools", true, accel(app, "dev_tools").as_deref())?,
         ],
     )?;
 
+    // Options menu: persistent boolean app settings, mirrored with the tray
+    let is_muted = crate::commands::is_muted().unwrap_or(false);
+    let is_focus_mode = crate::tray::is_focus_mode_enabled();
+    let start_at_login = app.autolaunch().is_enabled().unwrap_or(false);
+
+    let toggle_mute_i = CheckMenuItem::with_id(
+        app, "toggle_mute", "Mute Notifications", true, is_muted, accel(app, "toggle_mute").as_deref(),
+    )?;
+    let toggle_focus_i = CheckMenuItem::with_id(
+        app, "toggle_focus", "Focus Mode", true, is_focus_mode, accel(app, "toggle_focus").as_deref(),
+    )?;
+    let start_at_login_i = CheckMenuItem::with_id(
+        app, "start_at_login", "Start at Login", true, start_at_login, None::<&str>,
+    )?;
+
+    let options_menu = Submenu::with_items(
+        app,
+        "Options",
+        true,
+        &[&toggle_mute_i, &toggle_focus_i, &PredefinedMenuItem::separator(app)?, &start_at_login_i],
+    )?;
+
+    if let Some(registry) = app.try_state::<MenuRegistryState>() {
+        let mut registry = registry.lock().unwrap();
+        registry.register_check("toggle_mute", toggle_mute_i);
+        registry.register_check("toggle_focus", toggle_focus_i);
+        registry.register_check("start_at_login", start_at_login_i);
+    }
+
     // Help menu
     let help_menu = Submenu::with_items(
         app,
@@ -72,6 +121,17 @@ pub fn create_menu(app: &AppHandle<Wry>) -> Result<Menu<Wry>, Box<dyn std::error
         ],
     )?;
 
+    if let Some(registry) = app.try_state::<MenuRegistryState>() {
+        let mut registry = registry.lock().unwrap();
+        for submenu in [&view_menu, &help_menu] {
+            for item in submenu.items()? {
+                if let Some(item) = item.as_menuitem() {
+                    registry.register(item.id().as_ref(), item.clone());
+                }
+            }
+        }
+    }
+
     // macOS App menu
     #[cfg(target_os = "macos")]
     {
@@ -98,7 +158,7 @@ pub fn create_menu(app: &AppHandle<Wry>) -> Result<Menu<Wry>, Box<dyn std::error
                     }),
                 )?,
                 &PredefinedMenuItem::separator(app)?,
-                &MenuItem::with_id(app, "settings", "Settings...", true, Some("CommandOrControl+,"))?,
+                &MenuItem::with_id(app, "settings", "Settings...", true, accel(app, "settings").as_deref())?,
                 &PredefinedMenuItem::separator(app)?,
                 &PredefinedMenuItem::services(app, Some("Services"))?,
                 &PredefinedMenuItem::separator(app)?,
@@ -124,14 +184,14 @@ pub fn create_menu(app: &AppHandle<Wry>) -> Result<Menu<Wry>, Box<dyn std::error
 
         return Ok(Menu::with_items(
             app,
-            &[&app_menu, &file_menu, &edit_menu, &view_menu, &window_menu, &help_menu],
+            &[&app_menu, &file_menu, &edit_menu, &view_menu, &options_menu, &window_menu, &help_menu],
         )?);
     }
 
     #[cfg(not(target_os = "macos"))]
     Ok(Menu::with_items(
         app,
-        &[&file_menu, &edit_menu, &view_menu, &help_menu],
+        &[&file_menu, &edit_menu, &view_menu, &options_menu, &help_menu],
     )?)
 }
 
@@ -187,6 +247,19 @@ pub fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, event_id: &str) {
         "about" => {
             let _ = window.emit("menu:about", ());
         }
+        // Mute/focus-mode/start-at-login are shared with the tray menu, so
+        // the Options submenu reuses the tray's dispatch for them.
+        "toggle_mute" | "toggle_focus" | "start_at_login" => {
+            crate::tray::handle_tray_menu_event(app, event_id);
+        }
         _ => {}
     }
 }
+
+/// Rebuild the app menu from the current keymap and install it, e.g. after
+/// `keymap::set_keybinding` changes an accelerator
+pub fn rebuild_menu(app: &AppHandle<Wry>) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = create_menu(app)?;
+    app.set_menu(menu)?;
+    Ok(())
+}
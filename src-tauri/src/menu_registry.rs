@@ -0,0 +1,109 @@
+//! Registry of menu items that can be updated in place
+//!
+//! `create_menu` and the tray menu build every `MenuItem`/`CheckMenuItem` once
+//! with an explicit ID via `with_id`. This module keeps a handle to each of
+//! those items (keyed by that same ID) in Tauri managed state so the enabled
+//! state, label, or checked state of a single item can be updated without
+//! rebuilding the menu it lives in.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::menu::{CheckMenuItem, MenuItem};
+use tauri::{State, Wry};
+
+/// A registered menu item handle, kept as whichever concrete type it was
+/// created with so the right setter is available.
+enum RegisteredItem {
+    Normal(MenuItem<Wry>),
+    Check(CheckMenuItem<Wry>),
+}
+
+impl RegisteredItem {
+    fn set_enabled(&self, enabled: bool) -> tauri::Result<()> {
+        match self {
+            RegisteredItem::Normal(item) => item.set_enabled(enabled),
+            RegisteredItem::Check(item) => item.set_enabled(enabled),
+        }
+    }
+
+    fn set_text(&self, text: &str) -> tauri::Result<()> {
+        match self {
+            RegisteredItem::Normal(item) => item.set_text(text),
+            RegisteredItem::Check(item) => item.set_text(text),
+        }
+    }
+
+    fn set_checked(&self, checked: bool) -> Result<(), String> {
+        match self {
+            RegisteredItem::Check(item) => item.set_checked(checked).map_err(|e| e.to_string()),
+            RegisteredItem::Normal(_) => Err("menu item is not checkable".to_string()),
+        }
+    }
+}
+
+/// Registry of every menu item created with an explicit ID, keyed by that ID
+#[derive(Default)]
+pub struct MenuRegistry {
+    items: HashMap<String, RegisteredItem>,
+}
+
+impl MenuRegistry {
+    pub fn register(&mut self, id: impl Into<String>, item: MenuItem<Wry>) {
+        self.items.insert(id.into(), RegisteredItem::Normal(item));
+    }
+
+    pub fn register_check(&mut self, id: impl Into<String>, item: CheckMenuItem<Wry>) {
+        self.items.insert(id.into(), RegisteredItem::Check(item));
+    }
+
+    fn get(&self, id: &str) -> Result<&RegisteredItem, String> {
+        self.items
+            .get(id)
+            .ok_or_else(|| format!("Unknown menu item: {}", id))
+    }
+
+    pub fn set_enabled(&self, id: &str, enabled: bool) -> Result<(), String> {
+        self.get(id)?.set_enabled(enabled).map_err(|e| e.to_string())
+    }
+
+    pub fn set_text(&self, id: &str, text: &str) -> Result<(), String> {
+        self.get(id)?.set_text(text).map_err(|e| e.to_string())
+    }
+
+    pub fn set_checked(&self, id: &str, checked: bool) -> Result<(), String> {
+        self.get(id)?.set_checked(checked)
+    }
+}
+
+/// Managed state wrapper, mirroring how other mutable module state is stored
+pub type MenuRegistryState = Mutex<MenuRegistry>;
+
+/// Enable or disable a registered menu item by ID
+#[tauri::command]
+pub fn menu_set_enabled(
+    registry: State<MenuRegistryState>,
+    id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    registry.lock().unwrap().set_enabled(&id, enabled)
+}
+
+/// Change the label of a registered menu item by ID
+#[tauri::command]
+pub fn menu_set_text(
+    registry: State<MenuRegistryState>,
+    id: String,
+    text: String,
+) -> Result<(), String> {
+    registry.lock().unwrap().set_text(&id, &text)
+}
+
+/// Check or uncheck a registered `CheckMenuItem` by ID
+#[tauri::command]
+pub fn menu_set_checked(
+    registry: State<MenuRegistryState>,
+    id: String,
+    checked: bool,
+) -> Result<(), String> {
+    registry.lock().unwrap().set_checked(&id, checked)
+}
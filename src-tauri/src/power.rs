@@ -1,83 +1,132 @@
-use tauri::{AppHandle, Manager};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 
+#[cfg(target_os = "macos")]
+static CAFFEINATE_CHILD: Mutex<Option<std::process::Child>> = Mutex::new(None);
+
+#[cfg(target_os = "linux")]
+static SLEEP_INHIBITOR_FD: Mutex<Option<dbus::arg::OwnedFd>> = Mutex::new(None);
+
+#[cfg(target_os = "windows")]
 static PREVENTING_SLEEP: AtomicBool = AtomicBool::new(false);
 
-/// Prevent the system from going to sleep
-/// Useful during voice calls or screen sharing
+/// Prevent the system from going to sleep. Useful during voice calls or
+/// screen sharing. Holds a real, releasable inhibitor for the duration —
+/// see `allow_sleep` for how each platform's lock is released.
 #[tauri::command]
 pub fn prevent_sleep() -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
-        use std::process::Command;
-        // Use caffeinate on macOS to prevent sleep
-        // Note: In a real implementation, you'd want to track the PID to kill it later
-        // For now, we use a simple approach with assertion
-        let output = Command::new("caffeinate")
-            .args(&["-d", "-i", "-s", "-u", "-t", "1"])
-            .output()
-            .map_err(|e| format!("Failed to prevent sleep: {}", e))?;
-        
-        if !output.status.success() {
-            return Err("Failed to prevent sleep: caffeinate command failed".to_string());
+        let mut child = CAFFEINATE_CHILD.lock().unwrap();
+        if child.is_some() {
+            return Ok(());
         }
+
+        // No `-t`, so this runs (and keeps inhibiting) until killed
+        *child = Some(
+            std::process::Command::new("caffeinate")
+                .args(["-d", "-i", "-s"])
+                .spawn()
+                .map_err(|e| format!("Failed to prevent sleep: {}", e))?,
+        );
     }
-    
+
     #[cfg(target_os = "windows")]
     {
         use windows_sys::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS, ES_SYSTEM_REQUIRED, ES_DISPLAY_REQUIRED};
         unsafe {
             SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED);
         }
+        PREVENTING_SLEEP.store(true, Ordering::Relaxed);
     }
-    
+
     #[cfg(target_os = "linux")]
     {
-        // On Linux, we would use dbus to call systemd-logind or similar
-        // For now, this is a placeholder
-        use std::process::Command;
-        let _ = Command::new("systemctl")
-            .args(&["--user", "inhibit", "--what=handle-lid-switch:sleep:idle", "--who=Hearth", "--why=Voice call in progress", "--mode=block"])
-            .spawn()
+        use dbus::arg::OwnedFd;
+        use dbus::blocking::Connection;
+
+        let mut fd = SLEEP_INHIBITOR_FD.lock().unwrap();
+        if fd.is_some() {
+            return Ok(());
+        }
+
+        // logind hands back a file descriptor; the inhibitor is released the
+        // moment that fd is closed, so holding onto it *is* the lock
+        let conn = Connection::new_system().map_err(|e| format!("Failed to connect to D-Bus: {}", e))?;
+        let login1 = conn.with_proxy("org.freedesktop.login1", "/org/freedesktop/login1", Duration::from_millis(500));
+        let (inhibitor,): (OwnedFd,) = login1
+            .method_call(
+                "org.freedesktop.login1.Manager",
+                "Inhibit",
+                ("sleep:idle", "Hearth", "Voice call in progress", "block"),
+            )
             .map_err(|e| format!("Failed to prevent sleep: {}", e))?;
+
+        *fd = Some(inhibitor);
     }
-    
-    PREVENTING_SLEEP.store(true, Ordering::Relaxed);
+
     Ok(())
 }
 
-/// Allow the system to sleep again
+/// Allow the system to sleep again, releasing whatever lock `prevent_sleep` is holding
 #[tauri::command]
 pub fn allow_sleep() -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
-        // On macOS, caffeinate with -t 1 only prevents sleep for 1 second
-        // For a persistent prevent, you'd need to run caffeinate in the background
-        // and kill it when allowing sleep. This is simplified for the example.
+        if let Some(mut child) = CAFFEINATE_CHILD.lock().unwrap().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
     }
-    
+
     #[cfg(target_os = "windows")]
     {
         use windows_sys::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS};
         unsafe {
             SetThreadExecutionState(ES_CONTINUOUS);
         }
+        PREVENTING_SLEEP.store(false, Ordering::Relaxed);
     }
-    
+
     #[cfg(target_os = "linux")]
     {
-        // On Linux, the inhibition process would need to be killed
-        // This is a placeholder implementation
+        // Dropping the fd is what actually releases the inhibitor
+        SLEEP_INHIBITOR_FD.lock().unwrap().take();
     }
-    
-    PREVENTING_SLEEP.store(false, Ordering::Relaxed);
+
     Ok(())
 }
 
-/// Check if sleep is currently being prevented
+/// Whether sleep is currently being prevented. On macOS and Linux this
+/// reflects the real state of the underlying child/lock rather than a
+/// separate flag, so a `caffeinate` process that died out from under us
+/// doesn't report a false inhibition. On Windows it is the flag set by
+/// `prevent_sleep`/`allow_sleep`.
 #[tauri::command]
 pub fn is_sleep_prevented() -> bool {
-    PREVENTING_SLEEP.load(Ordering::Relaxed)
+    #[cfg(target_os = "macos")]
+    {
+        let mut child = CAFFEINATE_CHILD.lock().unwrap();
+        match child.as_mut().map(|c| c.try_wait()) {
+            Some(Ok(None)) => true, // still running
+            _ => {
+                *child = None;
+                false
+            }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        PREVENTING_SLEEP.load(Ordering::Relaxed)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        SLEEP_INHIBITOR_FD.lock().unwrap().is_some()
+    }
 }
 
 /// Get system power/battery status
@@ -237,7 +286,7 @@ pub fn get_power_status() -> Result<PowerStatus, String> {
 }
 
 /// Power status information
-#[derive(serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct PowerStatus {
     pub is_ac_power: bool,
     pub is_charging: bool,
@@ -245,3 +294,151 @@ pub struct PowerStatus {
     pub time_remaining: Option<String>,
     pub is_power_save_mode: bool,
 }
+
+// ============================================================================
+// Background power-state monitoring
+// ============================================================================
+
+const POWER_POLL_INTERVAL_MS: u64 = 15_000;
+
+/// Battery percentages worth waking the frontend up for when crossed on the
+/// way down
+const BATTERY_ALERT_THRESHOLDS: [u8; 2] = [20, 10];
+
+/// Holds the running flag for the background monitor thread, if started.
+/// Mirrors `activity::ActivityMonitorState`.
+pub type PowerMonitorState = Mutex<Option<Arc<AtomicBool>>>;
+
+fn emit_event<R: Runtime, S: serde::Serialize + Clone>(app: &AppHandle<R>, event: &str, payload: S) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(event, payload);
+    }
+}
+
+/// Whether `next` differs from `prev` in a way worth emitting: an AC/battery
+/// switch, charging starting or stopping, or the battery percentage
+/// crossing one of `BATTERY_ALERT_THRESHOLDS` on the way down
+fn is_meaningful_change(prev: &PowerStatus, next: &PowerStatus) -> bool {
+    if prev.is_ac_power != next.is_ac_power || prev.is_charging != next.is_charging {
+        return true;
+    }
+
+    if let (Some(before), Some(after)) = (prev.battery_percentage, next.battery_percentage) {
+        if BATTERY_ALERT_THRESHOLDS.iter().any(|&threshold| before > threshold && after <= threshold) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Start the background power monitor, if it isn't already running. Emits
+/// `power:changed` with the new `PowerStatus` only on meaningful
+/// transitions, not on every poll.
+#[tauri::command]
+pub fn start_power_monitoring<R: Runtime>(app: AppHandle<R>, state: State<PowerMonitorState>) {
+    let mut guard = state.lock().unwrap();
+    if guard.is_some() {
+        return;
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    spawn_monitor(app, running.clone());
+    *guard = Some(running);
+}
+
+/// Stop the background power monitor, if running
+#[tauri::command]
+pub fn stop_power_monitoring(state: State<PowerMonitorState>) {
+    if let Some(running) = state.lock().unwrap().take() {
+        running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Start the monitor if it isn't already running, without requiring a
+/// frontend-held `State` handle — used internally so a deferred
+/// low-battery update download is guaranteed to notice AC power coming
+/// back even if nothing else has opted into monitoring yet
+pub(crate) fn ensure_monitoring_started<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<PowerMonitorState>() else { return };
+    let mut guard = state.lock().unwrap();
+    if guard.is_some() {
+        return;
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    spawn_monitor(app.clone(), running.clone());
+    *guard = Some(running);
+}
+
+/// React to a status change: emit it if meaningful, and wake up any
+/// download that's been waiting on AC power specifically
+fn handle_status_update<R: Runtime>(app: &AppHandle<R>, last_status: &Option<PowerStatus>, status: &PowerStatus) {
+    let changed = last_status.as_ref().map(|prev| is_meaningful_change(prev, status)).unwrap_or(true);
+    if changed {
+        emit_event(app, "power:changed", status.clone());
+    }
+
+    let was_on_battery = last_status.as_ref().map(|prev| !prev.is_ac_power).unwrap_or(false);
+    if was_on_battery && status.is_ac_power {
+        crate::updater::resume_pending_download_if_any(app);
+    }
+}
+
+fn spawn_monitor<R: Runtime>(app: AppHandle<R>, running: Arc<AtomicBool>) {
+    std::thread::spawn(move || {
+        #[cfg(target_os = "linux")]
+        if linux_upower_watch(&app, &running) {
+            return;
+        }
+
+        // Portable fallback: poll the same per-OS status code everything
+        // else uses, and diff it ourselves
+        let mut last_status = get_power_status().ok();
+
+        while running.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(POWER_POLL_INTERVAL_MS));
+            if !running.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let Ok(status) = get_power_status() else { continue };
+            handle_status_update(&app, &last_status, &status);
+            last_status = Some(status);
+        }
+    });
+}
+
+/// Subscribe to UPower's `PropertiesChanged` signal on the display device
+/// and re-read the status when a signal arrives, or after at most one
+/// second without one. Returns `true` if the watch ran (until `running`
+/// was cleared); `false` if the system bus couldn't be reached or the
+/// match couldn't be added, so the caller falls back to the 15s poll.
+/// Whether UPower itself is running is not checked.
+#[cfg(target_os = "linux")]
+fn linux_upower_watch<R: Runtime>(app: &AppHandle<R>, running: &Arc<AtomicBool>) -> bool {
+    use dbus::blocking::Connection;
+    use dbus::message::MatchRule;
+
+    let Ok(conn) = Connection::new_system() else { return false };
+
+    let rule = MatchRule::new_signal("org.freedesktop.DBus.Properties", "PropertiesChanged")
+        .with_path("/org/freedesktop/UPower/devices/DisplayDevice");
+    if conn.add_match_no_cb(&rule.match_str()).is_err() {
+        return false;
+    }
+
+    let mut last_status = get_power_status().ok();
+
+    while running.load(Ordering::SeqCst) {
+        // Blocks up to 1s waiting for a signal, but also doubles as our
+        // poll of `running` so `stop_power_monitoring` is noticed promptly
+        let _ = conn.process(Duration::from_millis(1000));
+
+        let Ok(status) = get_power_status() else { continue };
+        handle_status_update(app, &last_status, &status);
+        last_status = Some(status);
+    }
+
+    true
+}
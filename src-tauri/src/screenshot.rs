@@ -1,4 +1,49 @@
-use tauri::{AppHandle, Manager};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// The most recently captured screenshot, for a quick "view last capture" affordance
+pub(crate) type LastScreenshotState = Mutex<Option<ScreenshotInfo>>;
+
+pub(crate) fn screenshots_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let dir = app_dir.join("screenshots");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+pub(crate) fn timestamped_path(dir: &Path, ext: &str) -> PathBuf {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    dir.join(format!("screenshot_{}.{}", timestamp, ext))
+}
+
+/// Try each `(binary, args)` candidate in order, returning on the first
+/// that spawns and exits successfully. Lets us fall back across desktop
+/// screenshot tools without caring which ones are actually installed.
+#[cfg(target_os = "linux")]
+fn run_first_success(candidates: &[(&str, Vec<String>)]) -> bool {
+    for (bin, args) in candidates {
+        if matches!(std::process::Command::new(bin).args(args).output(), Ok(output) if output.status.success()) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn session_is_wayland() -> bool {
+    std::env::var("XDG_SESSION_TYPE").map(|v| v.eq_ignore_ascii_case("wayland")).unwrap_or(false)
+        || std::env::var("WAYLAND_DISPLAY").is_ok()
+}
+
+#[cfg(target_os = "linux")]
+fn current_desktop() -> String {
+    std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default().to_lowercase()
+}
 
 /// Capture a screenshot of the entire screen
 #[tauri::command]
@@ -6,215 +51,358 @@ pub async fn capture_screenshot(app: AppHandle) -> Result<String, String> {
     #[cfg(target_os = "macos")]
     {
         use std::process::Command;
-        use std::path::PathBuf;
-        
-        // Get the app data directory
-        let app_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-        
-        let screenshots_dir = app_dir.join("screenshots");
-        std::fs::create_dir_all(&screenshots_dir)
-            .map_err(|e| format!("Failed to create screenshots dir: {}", e))?;
-        
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        let filename = format!("screenshot_{}.png", timestamp);
-        let filepath = screenshots_dir.join(&filename);
-        
-        // Use screencapture on macOS
+
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+
         let output = Command::new("screencapture")
-            .args(&["-x", filepath.to_str().unwrap()])
+            .args(["-x", filepath.to_str().unwrap()])
             .output()
             .map_err(|e| format!("Failed to capture screenshot: {}", e))?;
-        
+
         if !output.status.success() {
             return Err("Screenshot capture failed".to_string());
         }
-        
+
+        announce_capture(&app, &filepath);
         Ok(filepath.to_string_lossy().to_string())
     }
-    
+
     #[cfg(target_os = "windows")]
     {
-        use windows_sys::Win32::Graphics::Gdi::{GetDC, CreateCompatibleDC, CreateCompatibleBitmap, SelectObject, BitBlt, SRCCOPY, GetDIBits, BITMAPINFOHEADER, BI_RGB, DeleteObject, DeleteDC, ReleaseDC};
-        use windows_sys::Win32::UI::WindowsAndMessaging::GetSystemMetrics;
-        use windows_sys::Win32::UI::WindowsAndMessaging::{SM_CXSCREEN, SM_CYSCREEN};
-        use std::path::PathBuf;
-        use std::fs::File;
-        use std::io::Write;
-        
-        unsafe {
-            // Get screen dimensions
-            let screen_width = GetSystemMetrics(SM_CXSCREEN);
-            let screen_height = GetSystemMetrics(SM_CYSCREEN);
-            
-            // Get device context
-            let hwnd = std::ptr::null_mut();
-            let hdc_screen = GetDC(hwnd);
-            let hdc_mem = CreateCompatibleDC(hdc_screen);
-            
-            // Create bitmap
-            let hbitmap = CreateCompatibleBitmap(hdc_screen, screen_width, screen_height);
-            SelectObject(hdc_mem, hbitmap as *mut _);
-            
-            // Copy screen to bitmap
-            BitBlt(hdc_mem, 0, 0, screen_width, screen_height, hdc_screen, 0, 0, SRCCOPY);
-            
-            // Get bitmap bits
-            let mut bmi = BITMAPINFOHEADER {
-                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-                biWidth: screen_width,
-                biHeight: -screen_height, // Negative for top-down
-                biPlanes: 1,
-                biBitCount: 24,
-                biCompression: BI_RGB,
-                biSizeImage: 0,
-                biXPelsPerMeter: 0,
-                biYPelsPerMeter: 0,
-                biClrUsed: 0,
-                biClrImportant: 0,
-            };
-            
-            let row_size = ((screen_width * 3 + 3) / 4) * 4;
-            let image_size = row_size * screen_height;
-            let mut buffer: Vec<u8> = vec![0; image_size as usize];
-            
-            GetDIBits(hdc_mem, hbitmap, 0, screen_height as u32, buffer.as_mut_ptr() as *mut _, &mut bmi as *mut _ as *mut _, 0);
-            
-            // Clean up GDI objects
-            DeleteObject(hbitmap as *mut _);
-            DeleteDC(hdc_mem);
-            ReleaseDC(hwnd, hdc_screen);
-            
-            // Convert to PNG (simplified - in production use a proper PNG library)
-            // For now, save as BMP
-            let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-            let screenshots_dir = app_dir.join("screenshots");
-            std::fs::create_dir_all(&screenshots_dir).map_err(|e| e.to_string())?;
-            
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            
-            let filepath = screenshots_dir.join(format!("screenshot_{}.bmp", timestamp));
-            let mut file = File::create(&filepath).map_err(|e| e.to_string())?;
-            
-            // Write BMP header
-            let file_size = 54 + image_size as u32;
-            let header: Vec<u8> = vec![
-                0x42, 0x4D, // BM
-                (file_size & 0xFF) as u8, ((file_size >> 8) & 0xFF) as u8,
-                ((file_size >> 16) & 0xFF) as u8, ((file_size >> 24) & 0xFF) as u8,
-                0, 0, 0, 0, // Reserved
-                54, 0, 0, 0, // Offset to pixel data
-                40, 0, 0, 0, // DIB header size
-                (screen_width & 0xFF) as u8, ((screen_width >> 8) & 0xFF) as u8,
-                ((screen_width >> 16) & 0xFF) as u8, ((screen_width >> 24) & 0xFF) as u8,
-                (screen_height & 0xFF) as u8, ((screen_height >> 8) & 0xFF) as u8,
-                ((screen_height >> 16) & 0xFF) as u8, ((screen_height >> 24) & 0xFF) as u8,
-                1, 0, // Planes
-                24, 0, // Bits per pixel
-                0, 0, 0, 0, // Compression
-                0, 0, 0, 0, // Image size
-                0, 0, 0, 0, // X pixels per meter
-                0, 0, 0, 0, // Y pixels per meter
-                0, 0, 0, 0, // Colors in color table
-                0, 0, 0, 0, // Important color count
-            ];
-            
-            file.write_all(&header).map_err(|e| e.to_string())?;
-            file.write_all(&buffer).map_err(|e| e.to_string())?;
-            
-            Ok(filepath.to_string_lossy().to_string())
-        }
+        use windows_sys::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
+
+        let screen_width = unsafe { GetSystemMetrics(SM_CXSCREEN) };
+        let screen_height = unsafe { GetSystemMetrics(SM_CYSCREEN) };
+
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+        capture_rect_windows(0, 0, screen_width, screen_height, &filepath)?;
+
+        announce_capture(&app, &filepath);
+        Ok(filepath.to_string_lossy().to_string())
     }
-    
+
     #[cfg(target_os = "linux")]
     {
-        use std::process::Command;
-        use std::path::PathBuf;
-        
-        let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-        let screenshots_dir = app_dir.join("screenshots");
-        std::fs::create_dir_all(&screenshots_dir).map_err(|e| e.to_string())?;
-        
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        let filepath = screenshots_dir.join(format!("screenshot_{}.png", timestamp));
-        
-        // Try gnome-screenshot first, then fallback to import (ImageMagick)
-        let result = Command::new("gnome-screenshot")
-            .args(&["-f", filepath.to_str().unwrap()])
-            .output();
-        
-        if result.is_err() || !result.unwrap().status.success() {
-            // Fallback to ImageMagick's import
-            Command::new("import")
-                .args(&["-window", "root", filepath.to_str().unwrap()])
-                .output()
-                .map_err(|e| format!("Failed to capture screenshot: {}", e))?;
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+        let path_str = filepath.to_string_lossy().to_string();
+
+        let captured = if session_is_wayland() {
+            run_first_success(&[("grim", vec![path_str.clone()])])
+        } else {
+            run_first_success(&[
+                ("gnome-screenshot", vec!["-f".to_string(), path_str.clone()]),
+                ("maim", vec![path_str.clone()]),
+                ("import", vec!["-window".to_string(), "root".to_string(), path_str.clone()]),
+            ])
+        };
+
+        if !captured {
+            return Err("No supported screenshot tool found (install grim on Wayland, or gnome-screenshot/maim/ImageMagick on X11)".to_string());
         }
-        
-        Ok(filepath.to_string_lossy().to_string())
+
+        announce_capture(&app, &filepath);
+        Ok(path_str)
     }
 }
 
-/// Capture a screenshot of a specific window
+/// Record the capture as the "last screenshot" and emit `screenshot-captured`
+/// with its info so the frontend can show a transient thumbnail toast — the
+/// toast's Reveal/Open/Copy buttons call straight back into
+/// `reveal_in_folder`/`open_file`/`clipboard_write_image` over IPC, the same
+/// as any other frontend-triggered action, rather than this relying on
+/// native OS notification action buttons. Also fires (or queues, respecting
+/// mute/focus mode/the DND schedule) a plain notification, since not every
+/// capture happens with the window focused. Best-effort throughout — a
+/// screenshot having been saved successfully shouldn't be undone by any of
+/// this failing.
+fn announce_capture(app: &AppHandle, filepath: &Path) {
+    let Ok(info) = screenshot_info(filepath) else { return };
+
+    if let Some(state) = app.try_state::<LastScreenshotState>() {
+        *state.lock().unwrap() = Some(info.clone());
+    }
+
+    let _ = app.emit("screenshot-captured", &info);
+
+    if crate::dnd::is_suppressed(app) {
+        if let Some(queue) = app.try_state::<crate::dnd::NotificationQueueState>() {
+            crate::dnd::queue_notification(&queue, "Screenshot saved".to_string(), info.filename);
+        }
+        return;
+    }
+
+    let _ = app.notification().builder().title("Screenshot saved").body(&info.filename).show();
+}
+
+fn screenshot_info(path: &Path) -> Result<ScreenshotInfo, String> {
+    let metadata = std::fs::metadata(path).map_err(|e| e.to_string())?;
+    let created = metadata.created().map_err(|e| e.to_string())?;
+    let created_at = created.duration_since(std::time::UNIX_EPOCH).map_err(|e| e.to_string())?.as_secs();
+
+    Ok(ScreenshotInfo {
+        filename: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        path: path.to_string_lossy().to_string(),
+        size: metadata.len(),
+        created_at,
+    })
+}
+
+/// The most recently captured screenshot's info, for a "view last capture" affordance
 #[tauri::command]
-pub async fn capture_window_screenshot(window_id: u64) -> Result<String, String> {
-    // Platform-specific window capture
-    // This is a simplified implementation
-    Err("Window-specific screenshot not implemented".to_string())
+pub fn last_screenshot(app: AppHandle) -> Option<ScreenshotInfo> {
+    app.try_state::<LastScreenshotState>()?.lock().unwrap().clone()
+}
+
+/// Capture the whole screen and copy it straight to the clipboard, for
+/// pasting directly into a chat message without a round-trip through the
+/// file manager
+#[tauri::command]
+pub async fn capture_screenshot_to_clipboard(app: AppHandle) -> Result<(), String> {
+    let path = capture_screenshot(app.clone()).await?;
+    crate::commands::clipboard_write_image(app, path).await
+}
+
+/// Capture a screenshot of a specific window. `window_id` is a CGWindowID
+/// on macOS, an HWND on Windows, and an X11 window ID on Linux X11
+/// (Wayland has no standard per-window capture API, so the whole screen
+/// is captured as the closest cross-compositor approximation).
+#[tauri::command]
+pub async fn capture_window_screenshot(app: AppHandle, window_id: u64) -> Result<String, String> {
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+
+        let output = Command::new("screencapture")
+            .args(["-x", "-l", &window_id.to_string(), filepath.to_str().unwrap()])
+            .output()
+            .map_err(|e| format!("Failed to capture window screenshot: {}", e))?;
+
+        if !output.status.success() {
+            return Err("Window screenshot capture failed".to_string());
+        }
+
+        announce_capture(&app, &filepath);
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::Foundation::{HWND, RECT};
+        use windows_sys::Win32::UI::WindowsAndMessaging::GetWindowRect;
+
+        let hwnd = window_id as HWND;
+        let mut rect = RECT { left: 0, top: 0, right: 0, bottom: 0 };
+        if unsafe { GetWindowRect(hwnd, &mut rect) } == 0 {
+            return Err("Failed to locate window".to_string());
+        }
+
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+        capture_rect_windows(rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top, &filepath)?;
+
+        announce_capture(&app, &filepath);
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+        let path_str = filepath.to_string_lossy().to_string();
+
+        let captured = if session_is_wayland() {
+            run_first_success(&[("grim", vec![path_str.clone()])])
+        } else {
+            run_first_success(&[("import", vec!["-window".to_string(), format!("0x{:x}", window_id), path_str.clone()])])
+        };
+
+        if !captured {
+            return Err("No supported window-screenshot tool found".to_string());
+        }
+
+        announce_capture(&app, &filepath);
+        Ok(path_str)
+    }
 }
 
 /// Capture a screenshot of a specific region
 #[tauri::command]
 pub async fn capture_region_screenshot(
+    app: AppHandle,
     x: i32,
     y: i32,
     width: i32,
     height: i32,
 ) -> Result<String, String> {
-    // Platform-specific region capture
-    Err("Region screenshot not implemented".to_string())
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+
+        let output = Command::new("screencapture")
+            .args(["-x", "-R", &format!("{},{},{},{}", x, y, width, height), filepath.to_str().unwrap()])
+            .output()
+            .map_err(|e| format!("Failed to capture region screenshot: {}", e))?;
+
+        if !output.status.success() {
+            return Err("Region screenshot capture failed".to_string());
+        }
+
+        announce_capture(&app, &filepath);
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+        capture_rect_windows(x, y, width, height, &filepath)?;
+
+        announce_capture(&app, &filepath);
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let dir = screenshots_dir(&app)?;
+        let filepath = timestamped_path(&dir, "png");
+        let path_str = filepath.to_string_lossy().to_string();
+
+        let captured = if session_is_wayland() {
+            // slurp prints the region as a `grim -g` geometry string
+            // ("X,Y WxH") directly, so just feed it straight through
+            let Ok(slurp) = std::process::Command::new("slurp")
+                .args([&format!("{},{} {}x{}", x, y, width, height)])
+                .output()
+            else {
+                return Err("slurp is required to select a region on Wayland".to_string());
+            };
+            let geometry = String::from_utf8_lossy(&slurp.stdout).trim().to_string();
+            let geometry = if geometry.is_empty() { format!("{},{} {}x{}", x, y, width, height) } else { geometry };
+
+            run_first_success(&[("grim", vec!["-g".to_string(), geometry, path_str.clone()])])
+        } else {
+            let desktop = current_desktop();
+            let geometry = format!("{}x{}+{}+{}", width, height, x, y);
+
+            let mut candidates = vec![("maim", vec!["-g".to_string(), geometry, path_str.clone()])];
+            if desktop.contains("gnome") {
+                candidates.push(("gnome-screenshot", vec!["-a".to_string(), "-f".to_string(), path_str.clone()]));
+            }
+            if desktop.contains("kde") || desktop.contains("plasma") {
+                candidates.push(("spectacle", vec!["-r".to_string(), "-b".to_string(), "-n".to_string(), "-o".to_string(), path_str.clone()]));
+            }
+            candidates.push(("import", vec!["-window".to_string(), "root".to_string(), "-crop".to_string(), format!("{}x{}+{}+{}", width, height, x, y), path_str.clone()]));
+            candidates.push(("flameshot", vec!["gui".to_string(), "-p".to_string(), path_str.clone()]));
+
+            run_first_success(&candidates)
+        };
+
+        if !captured {
+            return Err("No supported region-screenshot tool found (install slurp+grim on Wayland, or maim/gnome-screenshot/spectacle/flameshot on X11)".to_string());
+        }
+
+        announce_capture(&app, &filepath);
+        Ok(path_str)
+    }
+}
+
+/// BitBlt the given screen rectangle into a new compatible bitmap and save
+/// it as a PNG via the `image` crate. Shared by the full-screen, window,
+/// and region capture commands so they only differ in which rect they pass.
+#[cfg(target_os = "windows")]
+fn capture_rect_windows(x: i32, y: i32, width: i32, height: i32, filepath: &Path) -> Result<(), String> {
+    use image::{ImageFormat, RgbImage};
+    use windows_sys::Win32::Graphics::Gdi::{
+        BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits, ReleaseDC,
+        SelectObject, BITMAPINFOHEADER, BI_RGB, SRCCOPY,
+    };
+
+    let buffer: Vec<u8> = unsafe {
+        let hwnd = std::ptr::null_mut();
+        let hdc_screen = GetDC(hwnd);
+        let hdc_mem = CreateCompatibleDC(hdc_screen);
+
+        let hbitmap = CreateCompatibleBitmap(hdc_screen, width, height);
+        SelectObject(hdc_mem, hbitmap as *mut _);
+
+        BitBlt(hdc_mem, 0, 0, width, height, hdc_screen, x, y, SRCCOPY);
+
+        let mut bmi = BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            biHeight: -height, // Negative for top-down
+            biPlanes: 1,
+            biBitCount: 24,
+            biCompression: BI_RGB,
+            biSizeImage: 0,
+            biXPelsPerMeter: 0,
+            biYPelsPerMeter: 0,
+            biClrUsed: 0,
+            biClrImportant: 0,
+        };
+
+        let row_size = ((width * 3 + 3) / 4) * 4;
+        let image_size = row_size * height;
+        let mut buffer: Vec<u8> = vec![0; image_size as usize];
+
+        GetDIBits(hdc_mem, hbitmap, 0, height as u32, buffer.as_mut_ptr() as *mut _, &mut bmi as *mut _ as *mut _, 0);
+
+        DeleteObject(hbitmap as *mut _);
+        DeleteDC(hdc_mem);
+        ReleaseDC(hwnd, hdc_screen);
+
+        buffer
+    };
+
+    // GetDIBits rows are BGR and padded to a 4-byte stride; repack into a
+    // tightly-packed RGB buffer the `image` crate expects
+    let row_size = ((width * 3 + 3) / 4) * 4;
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    for row in 0..height {
+        let row_start = (row * row_size) as usize;
+        for col in 0..width {
+            let pixel = row_start + (col * 3) as usize;
+            rgb.push(buffer[pixel + 2]); // R
+            rgb.push(buffer[pixel + 1]); // G
+            rgb.push(buffer[pixel]); // B
+        }
+    }
+
+    let image = RgbImage::from_raw(width as u32, height as u32, rgb)
+        .ok_or_else(|| "Captured buffer didn't match the expected image dimensions".to_string())?;
+
+    image.save_with_format(filepath, ImageFormat::Png).map_err(|e| e.to_string())
 }
 
 /// Get the screenshots directory path
 #[tauri::command]
 pub fn get_screenshots_dir(app: AppHandle) -> Result<String, String> {
-    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let screenshots_dir = app_dir.join("screenshots");
-    std::fs::create_dir_all(&screenshots_dir).map_err(|e| e.to_string())?;
-    Ok(screenshots_dir.to_string_lossy().to_string())
+    let dir = screenshots_dir(&app)?;
+    Ok(dir.to_string_lossy().to_string())
 }
 
 /// List all screenshots
 #[tauri::command]
 pub fn list_screenshots(app: AppHandle) -> Result<Vec<ScreenshotInfo>, String> {
-    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let screenshots_dir = app_dir.join("screenshots");
-    
-    if !screenshots_dir.exists() {
-        return Ok(vec![]);
-    }
-    
+    let screenshots_dir = screenshots_dir(&app)?;
+
     let mut screenshots = vec![];
-    
+
     for entry in std::fs::read_dir(&screenshots_dir).map_err(|e| e.to_string())? {
         let entry = entry.map_err(|e| e.to_string())?;
         let path = entry.path();
-        
+
         if let Some(ext) = path.extension() {
-            if ext == "png" || ext == "jpg" || ext == "bmp" {
+            if ext == "png" || ext == "jpg" {
                 if let Ok(metadata) = entry.metadata() {
                     if let Ok(created) = metadata.created() {
                         if let Ok(duration) = created.duration_since(std::time::UNIX_EPOCH) {
@@ -230,10 +418,10 @@ pub fn list_screenshots(app: AppHandle) -> Result<Vec<ScreenshotInfo>, String> {
             }
         }
     }
-    
+
     // Sort by creation time, newest first
     screenshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-    
+
     Ok(screenshots)
 }
 
@@ -244,7 +432,7 @@ pub fn delete_screenshot(path: String) -> Result<(), String> {
 }
 
 /// Screenshot information
-#[derive(serde::Serialize)]
+#[derive(Clone, serde::Serialize)]
 pub struct ScreenshotInfo {
     pub filename: String,
     pub path: String,
@@ -0,0 +1,215 @@
+//! User-configurable, persisted global shortcuts
+//!
+//! The four global shortcuts used to be string literals registered
+//! directly in `main`'s `setup()`, so users couldn't rebind or disable
+//! them and a conflicting accelerator had nowhere to go. This module owns
+//! the action-id -> accelerator map (persisted via `tauri-plugin-store`,
+//! falling back to the defaults below), registers/unregisters them
+//! through `global_shortcut_manager`, and exposes commands to rebind or
+//! reset them. The side effects each action triggers (tray updates, DND
+//! replay, toast events) live here too, since they have to run no matter
+//! which accelerator is currently bound to the action.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, GlobalShortcutBuilder, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "shortcuts.json";
+
+/// The built-in defaults, matching what `main::setup` used to hardcode
+const DEFAULT_BINDINGS: &[(&str, &str)] = &[
+    ("toggle-window", "CommandOrControl+Shift+H"),
+    ("show-window", "CommandOrControl+Shift+S"),
+    ("toggle-mute", "CommandOrControl+Shift+M"),
+    ("toggle-focus", "CommandOrControl+Shift+F"),
+];
+
+fn default_bindings() -> HashMap<String, Option<String>> {
+    DEFAULT_BINDINGS
+        .iter()
+        .map(|(id, accel)| (id.to_string(), Some(accel.to_string())))
+        .collect()
+}
+
+/// Managed state holding the currently active bindings
+pub type ShortcutBindingsState = Mutex<HashMap<String, Option<String>>>;
+
+fn load_from_store<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, Option<String>> {
+    let mut map = default_bindings();
+
+    if let Ok(store) = app.store(STORE_FILE) {
+        if let Some(overrides) = store.get("bindings").and_then(|v| serde_json::from_value::<HashMap<String, Option<String>>>(v).ok()) {
+            for (id, accelerator) in overrides {
+                if map.contains_key(&id) {
+                    map.insert(id, accelerator);
+                }
+            }
+        }
+    }
+
+    map
+}
+
+fn save_to_store<R: Runtime>(app: &AppHandle<R>, map: &HashMap<String, Option<String>>) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    store.set("bindings", serde_json::json!(map));
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Run the side effects for `action_id`. These are the same closures
+/// `main::setup` used to register directly with `global_shortcut_manager`.
+fn run_action<R: Runtime>(app: &AppHandle<R>, action_id: &str) {
+    match action_id {
+        "toggle-window" => {
+            if let Some(window) = app.get_webview_window("main") {
+                if window.is_visible().unwrap_or(false) {
+                    let _ = window.hide();
+                } else {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+        }
+        "show-window" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        "toggle-mute" => {
+            let muted = crate::commands::toggle_mute().unwrap_or(false);
+            let _ = crate::tray::update_tray_mute_state(app, muted);
+            crate::dnd::flush_if_clear(app);
+
+            if let Some(window) = app.get_webview_window("main") {
+                let message = if muted { "Notifications muted" } else { "Notifications unmuted" };
+                let _ = window.emit("mute-state-changed", serde_json::json!({
+                    "muted": muted,
+                    "message": message
+                }));
+            }
+        }
+        "toggle-focus" => {
+            let active = crate::commands::toggle_focus_mode().unwrap_or(false);
+            let _ = crate::tray::update_tray_focus_state(app, active);
+            crate::dnd::flush_if_clear(app);
+
+            if let Some(window) = app.get_webview_window("main") {
+                let message = if active {
+                    "Focus mode enabled - only mentions and DMs"
+                } else {
+                    "Focus mode disabled"
+                };
+                let _ = window.emit("focus-mode-changed", serde_json::json!({
+                    "active": active,
+                    "message": message
+                }));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn register<R: Runtime>(app: &AppHandle<R>, action_id: &str, accelerator: &str) -> Result<(), String> {
+    let app_handle = app.clone();
+    let action_id = action_id.to_string();
+
+    app.global_shortcut_manager()
+        .register(accelerator, move || {
+            run_action(&app_handle, &action_id);
+        })
+        .map_err(|e| e.to_string())
+}
+
+fn unregister<R: Runtime>(app: &AppHandle<R>, accelerator: &str) {
+    let _ = app.global_shortcut_manager().unregister(accelerator);
+}
+
+fn register_all<R: Runtime>(app: &AppHandle<R>, bindings: &HashMap<String, Option<String>>) {
+    for (action_id, accelerator) in bindings {
+        if let Some(accelerator) = accelerator {
+            let _ = register(app, action_id, accelerator);
+        }
+    }
+}
+
+/// Load bindings from the store (or defaults) and register them. Call
+/// once during `setup()`, after the tray/menu are in place.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let bindings = load_from_store(app);
+    register_all(app, &bindings);
+    app.manage(Mutex::new(bindings) as ShortcutBindingsState);
+}
+
+/// Get the full current action-id -> accelerator map
+#[tauri::command]
+pub fn get_shortcut_bindings(state: tauri::State<ShortcutBindingsState>) -> HashMap<String, Option<String>> {
+    state.lock().unwrap().clone()
+}
+
+/// Look up the accelerator currently bound to a global-shortcut action,
+/// falling back to the baked-in default if managed state isn't available
+/// yet. Mirrors `keymap::accelerator_for`, for callers (e.g. the command
+/// palette) that want a single source of truth for what's displayed.
+pub fn accelerator_for<R: Runtime>(app: &AppHandle<R>, action_id: &str) -> Option<String> {
+    if let Some(state) = app.try_state::<ShortcutBindingsState>() {
+        return state.lock().unwrap().get(action_id).cloned().flatten();
+    }
+
+    DEFAULT_BINDINGS
+        .iter()
+        .find(|(id, _)| *id == action_id)
+        .map(|(_, accel)| accel.to_string())
+}
+
+/// Rebind (or clear, by passing `None`) a single action: unregisters the
+/// old accelerator, validates the new one isn't already bound to a
+/// different action, registers it, and persists the result
+#[tauri::command]
+pub fn set_shortcut_binding(
+    app: AppHandle,
+    state: tauri::State<ShortcutBindingsState>,
+    action_id: String,
+    accelerator: Option<String>,
+) -> Result<(), String> {
+    let mut map = state.lock().unwrap();
+
+    if !map.contains_key(&action_id) {
+        return Err(format!("Unknown shortcut action: {}", action_id));
+    }
+
+    if let Some(new_accelerator) = &accelerator {
+        if let Some((conflicting_action, _)) = map
+            .iter()
+            .find(|(id, bound)| *id != &action_id && bound.as_deref() == Some(new_accelerator.as_str()))
+        {
+            return Err(format!("\"{}\" is already bound to \"{}\"", new_accelerator, conflicting_action));
+        }
+    }
+
+    if let Some(old_accelerator) = map.get(&action_id).and_then(|a| a.as_deref()) {
+        unregister(&app, old_accelerator);
+    }
+
+    if let Some(new_accelerator) = &accelerator {
+        register(&app, &action_id, new_accelerator)?;
+    }
+
+    map.insert(action_id, accelerator);
+    save_to_store(&app, &map)
+}
+
+/// Reset every binding back to the built-in defaults
+#[tauri::command]
+pub fn reset_shortcut_bindings(app: AppHandle, state: tauri::State<ShortcutBindingsState>) -> Result<(), String> {
+    let mut map = state.lock().unwrap();
+
+    for accelerator in map.values().flatten() {
+        unregister(&app, accelerator);
+    }
+
+    *map = default_bindings();
+    register_all(&app, &map);
+    save_to_store(&app, &map)
+}
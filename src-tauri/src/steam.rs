@@ -0,0 +1,204 @@
+//! Steam library integration for accurate game names and icons
+//!
+//! Substring-matching a process name against the detection rules gets the
+//! wrong (or a generic) title for most Steam games. This reads Steam's own
+//! VDF manifests instead: `steamapps/libraryfolders.vdf` to discover every
+//! library path, then each library's `appmanifest_<appid>.acf` to map an
+//! install directory to its real game name, AppID, and CDN header art.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Runtime, State};
+
+/// An installed Steam app resolved from its manifest
+#[derive(Debug, Clone)]
+pub struct SteamApp {
+    pub app_id: String,
+    pub name: String,
+    pub install_dir: PathBuf,
+}
+
+/// Cached index of installed Steam apps, rebuilt on `refresh_steam_library`
+pub type SteamLibraryState = Mutex<Vec<SteamApp>>;
+
+/// Build the index from disk and store it in managed state. Call once
+/// during `setup()`.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    app.manage(Mutex::new(build_index()) as SteamLibraryState);
+}
+
+/// Rebuild the Steam library index, returning how many apps were found
+#[tauri::command]
+pub fn refresh_steam_library(state: State<SteamLibraryState>) -> usize {
+    let apps = build_index();
+    let count = apps.len();
+    *state.lock().unwrap() = apps;
+    count
+}
+
+/// Resolve an executable path to the Steam app whose install directory
+/// contains it, if any, returning its name, AppID, and header art URL
+pub(crate) fn match_installed_app(apps: &[SteamApp], exe_path: &Path) -> Option<(String, String, String)> {
+    apps.iter()
+        .find(|app| exe_path.starts_with(&app.install_dir))
+        .map(|app| (app.name.clone(), app.app_id.clone(), header_art_url(&app.app_id)))
+}
+
+/// Steam CDN header art for an AppID, used as rich-presence artwork
+fn header_art_url(app_id: &str) -> String {
+    format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}/header.jpg", app_id)
+}
+
+fn build_index() -> Vec<SteamApp> {
+    let Some(root) = steam_root() else {
+        return Vec::new();
+    };
+
+    library_folders(&root)
+        .iter()
+        .flat_map(|library| scan_library(library))
+        .collect()
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+/// The default Steam install directory for this platform
+fn steam_root() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        home_dir().map(|home| home.join("Library/Application Support/Steam"))
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("ProgramFiles(x86)")
+            .map(PathBuf::from)
+            .map(|p| p.join("Steam"))
+            .or_else(|| std::env::var_os("ProgramFiles").map(PathBuf::from).map(|p| p.join("Steam")))
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        home_dir().and_then(|home| {
+            let flatpak = home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam");
+            let classic = home.join(".steam/steam");
+            let xdg = home.join(".local/share/Steam");
+
+            [classic, xdg, flatpak].into_iter().find(|p| p.exists())
+        })
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Every Steam library path: the default root plus anything listed in
+/// `steamapps/libraryfolders.vdf`
+fn library_folders(root: &Path) -> Vec<PathBuf> {
+    let mut libraries = vec![root.to_path_buf()];
+
+    let vdf_path = root.join("steamapps").join("libraryfolders.vdf");
+    let Ok(contents) = std::fs::read_to_string(&vdf_path) else {
+        return libraries;
+    };
+
+    for (key, value) in quoted_pairs(&contents) {
+        // Modern format nests `"path" "..."` under a numbered block; the
+        // legacy format has the path directly as a numbered key's value
+        let looks_like_path = value.contains('/') || value.contains('\\');
+        let is_library_entry = key == "path" || (key.chars().all(|c| c.is_ascii_digit()) && looks_like_path);
+
+        if is_library_entry {
+            let path = PathBuf::from(value.replace("\\\\", "/"));
+            if path.exists() && !libraries.contains(&path) {
+                libraries.push(path);
+            }
+        }
+    }
+
+    libraries
+}
+
+/// Parse every `appmanifest_<appid>.acf` in a library's `steamapps` dir
+fn scan_library(library: &Path) -> Vec<SteamApp> {
+    let steamapps = library.join("steamapps");
+    let mut apps = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(&steamapps) else {
+        return apps;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_manifest = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with("appmanifest_") && n.ends_with(".acf"))
+            .unwrap_or(false);
+        if !is_manifest {
+            continue;
+        }
+
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let pairs = quoted_pairs(&contents);
+
+        let app_id = pairs.iter().find(|(k, _)| k == "appid").map(|(_, v)| v.clone());
+        let name = pairs.iter().find(|(k, _)| k == "name").map(|(_, v)| v.clone());
+        let install_dir = pairs.iter().find(|(k, _)| k == "installdir").map(|(_, v)| v.clone());
+
+        if let (Some(app_id), Some(name), Some(install_dir)) = (app_id, name, install_dir) {
+            apps.push(SteamApp {
+                app_id,
+                name,
+                install_dir: steamapps.join("common").join(install_dir),
+            });
+        }
+    }
+
+    apps
+}
+
+/// Extract every `"key" "value"` pair on its own line from a VDF
+/// (KeyValues) file. Good enough for the flat manifest/library files Steam
+/// writes without needing a full VDF grammar.
+fn quoted_pairs(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let tokens = quoted_tokens(line);
+            match tokens.as_slice() {
+                [key, value] => Some((key.clone(), value.clone())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn quoted_tokens(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '"' {
+            continue;
+        }
+
+        let mut token = String::new();
+        for next in chars.by_ref() {
+            if next == '"' {
+                break;
+            }
+            token.push(next);
+        }
+        tokens.push(token);
+    }
+
+    tokens
+}
@@ -1,13 +1,25 @@
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, Runtime, AppHandle, State,
+    Emitter, Manager, Runtime, AppHandle, State,
 };
+use tauri_plugin_autostart::ManagerExt;
+use tauri_plugin_notification::NotificationExt;
 use std::sync::atomic::{AtomicU32, AtomicBool, Ordering};
 
+use crate::menu_registry::MenuRegistryState;
+
 /// Global unread count for tray updates
 static UNREAD_COUNT: AtomicU32 = AtomicU32::new(0);
 
+/// Unread count that also counts as a mention/DM, used to keep the badge
+/// meaningful while focus mode is hiding everything else
+static MENTION_COUNT: AtomicU32 = AtomicU32::new(0);
+
+/// Whether the OS dock/taskbar badge should be kept in sync with the unread
+/// count, independent of the tray tooltip (which always updates)
+static BADGES_ENABLED: AtomicBool = AtomicBool::new(true);
+
 /// Global focus mode state
 static FOCUS_MODE_ENABLED: AtomicBool = AtomicBool::new(false);
 
@@ -21,68 +33,7 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::er
         .menu(&menu)
         .menu_on_left_click(false)
         .on_menu_event(move |app, event| {
-            match event.id().as_ref() {
-                "show" => {
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                    }
-                }
-                "hide" => {
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.hide();
-                    }
-                }
-                "toggle_mute" => {
-                    // Toggle mute state
-                    let muted = crate::commands::toggle_mute().unwrap_or(false);
-                    let focus_mode = FOCUS_MODE_ENABLED.load(Ordering::Relaxed);
-                    // Update the menu to reflect new state
-                    let _ = update_tray_menu(app, muted, focus_mode);
-                    
-                    // Show a notification toast when muting/unmuting
-                    if muted {
-                        let _ = app.notification()
-                            .builder()
-                            .title("Hearth")
-                            .body("Notifications muted")
-                            .show();
-                    }
-                }
-                "toggle_focus" => {
-                    // Toggle focus mode
-                    let current = FOCUS_MODE_ENABLED.load(Ordering::Relaxed);
-                    let new_state = !current;
-                    FOCUS_MODE_ENABLED.store(new_state, Ordering::Relaxed);
-                    
-                    let is_muted = crate::commands::is_muted().unwrap_or(false);
-                    let _ = update_tray_menu(app, is_muted, new_state);
-                    
-                    // Notify the UI about focus mode change
-                    if let Some(window) = app.get_webview_window("main") {
-                        let message = if new_state {
-                            "Focus mode enabled - only mentions and DMs"
-                        } else {
-                            "Focus mode disabled"
-                        };
-                        let _ = window.emit("focus-mode-changed", serde_json::json!({
-                            "active": new_state,
-                            "message": message
-                        }));
-                    }
-                    
-                    // Show system notification
-                    let _ = app.notification()
-                        .builder()
-                        .title("Hearth")
-                        .body(message)
-                        .show();
-                }
-                "quit" => {
-                    app.exit(0);
-                }
-                _ => {}
-            }
+            handle_tray_menu_event(app, event.id().as_ref());
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click {
@@ -107,6 +58,87 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// Handle a tray menu action by ID. Shared by the tray's own `on_menu_event`
+/// and by `command_palette::invoke_command`, so triggering "Toggle Mute"
+/// from the palette behaves identically to clicking it in the tray.
+pub fn handle_tray_menu_event<R: Runtime>(app: &AppHandle<R>, event_id: &str) {
+    match event_id {
+        "show" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        "hide" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.hide();
+            }
+        }
+        "toggle_mute" => {
+            let muted = crate::commands::toggle_mute().unwrap_or(false);
+            let focus_mode = FOCUS_MODE_ENABLED.load(Ordering::Relaxed);
+            let _ = update_tray_menu(app, muted, focus_mode);
+            crate::dnd::flush_if_clear(app);
+
+            if muted {
+                let _ = app.notification()
+                    .builder()
+                    .title("Hearth")
+                    .body("Notifications muted")
+                    .show();
+            }
+        }
+        "toggle_focus" => {
+            let current = FOCUS_MODE_ENABLED.load(Ordering::Relaxed);
+            let new_state = !current;
+            FOCUS_MODE_ENABLED.store(new_state, Ordering::Relaxed);
+            let _ = crate::commands::set_focus_mode(new_state);
+
+            let is_muted = crate::commands::is_muted().unwrap_or(false);
+            let _ = update_tray_menu(app, is_muted, new_state);
+            crate::dnd::flush_if_clear(app);
+
+            let message = if new_state {
+                "Focus mode enabled - only mentions and DMs"
+            } else {
+                "Focus mode disabled"
+            };
+
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.emit("focus-mode-changed", serde_json::json!({
+                    "active": new_state,
+                    "message": message
+                }));
+            }
+
+            let _ = app.notification()
+                .builder()
+                .title("Hearth")
+                .body(message)
+                .show();
+        }
+        "start_at_login" => {
+            let autolaunch = app.autolaunch();
+            let enabled = autolaunch.is_enabled().unwrap_or(false);
+            let result = if enabled {
+                autolaunch.disable()
+            } else {
+                autolaunch.enable()
+            };
+
+            if result.is_ok() {
+                if let Some(registry) = app.try_state::<MenuRegistryState>() {
+                    let _ = registry.lock().unwrap().set_checked("start_at_login", !enabled);
+                }
+            }
+        }
+        "quit" => {
+            app.exit(0);
+        }
+        _ => {}
+    }
+}
+
 fn create_tray_menu<R: Runtime>(
     app: &AppHandle<R>,
     is_muted: bool,
@@ -114,39 +146,70 @@ fn create_tray_menu<R: Runtime>(
     let show_i = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
     let hide_i = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
     let separator = PredefinedMenuItem::separator(app)?;
-    
-    let mute_text = if is_muted {
-        "Unmute Notifications"
-    } else {
-        "Mute Notifications"
-    };
-    let toggle_mute_i = MenuItem::with_id(app, "toggle_mute", mute_text, true, None::<&str>)?;
-    
-    // Focus mode toggle
+
     let focus_mode_enabled = FOCUS_MODE_ENABLED.load(Ordering::Relaxed);
-    let focus_text = if focus_mode_enabled {
-        "Exit Focus Mode"
-    } else {
-        "Enter Focus Mode"
-    };
-    let toggle_focus_i = MenuItem::with_id(app, "toggle_focus", focus_text, true, None::<&str>)?;
-    
+    let start_at_login = app.autolaunch().is_enabled().unwrap_or(false);
+
+    let toggle_mute_i = CheckMenuItem::with_id(
+        app, "toggle_mute", "Mute Notifications", true, is_muted, None::<&str>,
+    )?;
+    let toggle_focus_i = CheckMenuItem::with_id(
+        app, "toggle_focus", "Focus Mode", true, focus_mode_enabled, None::<&str>,
+    )?;
     let separator2 = PredefinedMenuItem::separator(app)?;
+    let start_at_login_i = CheckMenuItem::with_id(
+        app, "start_at_login", "Start at Login", true, start_at_login, None::<&str>,
+    )?;
+
+    let separator3 = PredefinedMenuItem::separator(app)?;
     let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
 
-    let menu = Menu::with_items(app, &[&show_i, &hide_i, &separator, &toggle_mute_i, &toggle_focus_i, &separator2, &quit_i])?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &show_i, &hide_i, &separator,
+            &toggle_mute_i, &toggle_focus_i, &separator2, &start_at_login_i,
+            &separator3, &quit_i,
+        ],
+    )?;
+
+    // Register every item with an explicit ID so later state flips can update
+    // the item in place instead of rebuilding this whole menu.
+    if let Some(registry) = app.try_state::<MenuRegistryState>() {
+        let mut registry = registry.lock().unwrap();
+        registry.register("show", show_i);
+        registry.register("hide", hide_i);
+        registry.register_check("toggle_mute", toggle_mute_i);
+        registry.register_check("toggle_focus", toggle_focus_i);
+        registry.register_check("start_at_login", start_at_login_i);
+        registry.register("quit", quit_i);
+    }
+
     Ok(menu)
 }
 
+/// Reflect the mute/focus-mode checkmarks in place via the menu registry
 fn update_tray_menu<R: Runtime>(
     app: &AppHandle<R>,
     is_muted: bool,
     is_focus_mode: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    if let Some(tray) = app.tray_by_id("main") {
-        let new_menu = create_tray_menu(app, is_muted)?;
-        tray.set_menu(Some(new_menu))?;
-    }
+    let registry = match app.try_state::<MenuRegistryState>() {
+        Some(registry) => registry,
+        // Fall back to a full rebuild if the registry hasn't been set up yet
+        None => {
+            if let Some(tray) = app.tray_by_id("main") {
+                let new_menu = create_tray_menu(app, is_muted)?;
+                tray.set_menu(Some(new_menu))?;
+            }
+            return Ok(());
+        }
+    };
+
+    let registry = registry.lock().unwrap();
+    registry.set_checked("toggle_mute", is_muted)?;
+    registry.set_checked("toggle_focus", is_focus_mode)?;
+
     Ok(())
 }
 
@@ -192,13 +255,17 @@ pub fn update_tray_tooltip<R: Runtime>(
     Ok(())
 }
 
-/// Set the unread message count and update tray
+/// Set the unread message count (and how many of those are mentions/DMs)
+/// and update the tray tooltip and OS badge
 pub fn set_unread_count<R: Runtime>(
     app: &AppHandle<R>,
     count: u32,
+    mention_count: u32,
 ) -> Result<(), Box<dyn std::error::Error>> {
     UNREAD_COUNT.store(count, Ordering::Relaxed);
+    MENTION_COUNT.store(mention_count, Ordering::Relaxed);
     update_tray_tooltip(app)?;
+    update_badge(app)?;
     Ok(())
 }
 
@@ -206,3 +273,131 @@ pub fn set_unread_count<R: Runtime>(
 pub fn get_unread_count() -> u32 {
     UNREAD_COUNT.load(Ordering::Relaxed)
 }
+
+/// Enable or disable the OS dock/taskbar badge independent of the tooltip
+pub fn set_badges_enabled<R: Runtime>(
+    app: &AppHandle<R>,
+    enabled: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    BADGES_ENABLED.store(enabled, Ordering::Relaxed);
+    update_badge(app)
+}
+
+/// Whether the OS badge is currently enabled
+pub fn are_badges_enabled() -> bool {
+    BADGES_ENABLED.load(Ordering::Relaxed)
+}
+
+/// The unread count the badge should actually display: in focus mode,
+/// non-mention unreads are invisible noise, so only mentions/DMs count
+fn badge_count() -> u32 {
+    if FOCUS_MODE_ENABLED.load(Ordering::Relaxed) {
+        MENTION_COUNT.load(Ordering::Relaxed)
+    } else {
+        UNREAD_COUNT.load(Ordering::Relaxed)
+    }
+}
+
+/// Sync the OS dock (macOS)/taskbar (Windows) badge to the current unread
+/// state. No-op on Linux window managers that don't support either API.
+fn update_badge<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::error::Error>> {
+    let window = match app.get_webview_window("main") {
+        Some(window) => window,
+        None => return Ok(()),
+    };
+
+    let count = if BADGES_ENABLED.load(Ordering::Relaxed) {
+        badge_count()
+    } else {
+        0
+    };
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    {
+        let badge = if count > 0 { Some(count as i64) } else { None };
+        window.set_badge_count(badge)?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if count > 0 {
+            window.set_overlay_icon(Some(badge_overlay_icon(count)?))?;
+        } else {
+            window.set_overlay_icon(None)?;
+        }
+    }
+
+    let _ = count;
+    Ok(())
+}
+
+/// Render a small red badge icon with the unread count for the Windows
+/// taskbar overlay. Counts above 9 are shown as "9+".
+#[cfg(target_os = "windows")]
+fn badge_overlay_icon(count: u32) -> Result<tauri::image::Image<'static>, Box<dyn std::error::Error>> {
+    const SIZE: usize = 16;
+    const RED: [u8; 4] = [0xe0, 0x30, 0x30, 0xff];
+    const WHITE: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+
+    // 3x5 bitmap font for the digits we can show ("0".."9" and "+")
+    const FONT: [[u8; 5]; 11] = [
+        [0b111, 0b101, 0b101, 0b101, 0b111], // 0
+        [0b010, 0b110, 0b010, 0b010, 0b111], // 1
+        [0b111, 0b001, 0b111, 0b100, 0b111], // 2
+        [0b111, 0b001, 0b111, 0b001, 0b111], // 3
+        [0b101, 0b101, 0b111, 0b001, 0b001], // 4
+        [0b111, 0b100, 0b111, 0b001, 0b111], // 5
+        [0b111, 0b100, 0b111, 0b101, 0b111], // 6
+        [0b111, 0b001, 0b001, 0b001, 0b001], // 7
+        [0b111, 0b101, 0b111, 0b101, 0b111], // 8
+        [0b111, 0b101, 0b111, 0b001, 0b111], // 9
+        [0b000, 0b010, 0b111, 0b010, 0b000], // +
+    ];
+
+    let mut buffer = vec![0u8; SIZE * SIZE * 4];
+
+    // Filled circle background
+    let center = SIZE as f32 / 2.0 - 0.5;
+    let radius = SIZE as f32 / 2.0 - 0.5;
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let dx = x as f32 - center;
+            let dy = y as f32 - center;
+            if dx * dx + dy * dy <= radius * radius {
+                let idx = (y * SIZE + x) * 4;
+                buffer[idx..idx + 4].copy_from_slice(&RED);
+            }
+        }
+    }
+
+    let glyphs: Vec<usize> = if count > 9 {
+        vec![9, 10] // "9+"
+    } else {
+        vec![count as usize]
+    };
+
+    let glyph_w = 3;
+    let glyph_h = 5;
+    let gap = 1;
+    let total_w = glyphs.len() * glyph_w + glyphs.len().saturating_sub(1) * gap;
+    let start_x = (SIZE - total_w) / 2;
+    let start_y = (SIZE - glyph_h) / 2;
+
+    for (i, &glyph) in glyphs.iter().enumerate() {
+        let ox = start_x + i * (glyph_w + gap);
+        for (row, bits) in FONT[glyph].iter().enumerate() {
+            for col in 0..glyph_w {
+                if bits & (1 << (glyph_w - 1 - col)) != 0 {
+                    let x = ox + col;
+                    let y = start_y + row;
+                    if x < SIZE && y < SIZE {
+                        let idx = (y * SIZE + x) * 4;
+                        buffer[idx..idx + 4].copy_from_slice(&WHITE);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(tauri::image::Image::new_owned(buffer, SIZE as u32, SIZE as u32))
+}
@@ -1,7 +1,25 @@
 use log::{info, warn, error};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, Manager};
-use tauri_plugin_updater::UpdaterExt;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::power;
+
+/// Where skip/defer preferences and the configurable check interval live
+const STORE_FILE: &str = "update_prefs.json";
+
+/// How often the background loop re-checks when the store has no
+/// `check_interval_secs` override
+const DEFAULT_CHECK_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+/// Battery percentage below which a download is held back unless `force`
+/// when the store has no `low_battery_threshold` override
+const DEFAULT_LOW_BATTERY_THRESHOLD: u8 = 20;
+
+/// Whether a download is waiting for AC power to resume automatically
+static PENDING_LOW_BATTERY_DOWNLOAD: Mutex<bool> = Mutex::new(false);
 
 /// Information about an available update
 #[derive(Clone, Serialize, Deserialize)]
@@ -20,25 +38,28 @@ pub struct UpdateProgress {
     pub percent: Option<f64>,
 }
 
+fn build_info(update: &Update) -> UpdateInfo {
+    UpdateInfo {
+        version: update.version.clone(),
+        current_version: env!("CARGO_PKG_VERSION").to_string(),
+        body: update.body.clone(),
+        date: update.date.map(|d| d.to_string()),
+    }
+}
+
 /// Check for updates and return info if available
 #[tauri::command]
 pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
     info!("Checking for updates...");
-    
+
     let updater = app.updater().map_err(|e| {
         error!("Failed to get updater: {}", e);
         e.to_string()
     })?;
-    
+
     match updater.check().await {
         Ok(Some(update)) => {
-            let current_version = env!("CARGO_PKG_VERSION").to_string();
-            let info = UpdateInfo {
-                version: update.version.clone(),
-                current_version,
-                body: update.body.clone(),
-                date: update.date.map(|d| d.to_string()),
-            };
+            let info = build_info(&update);
             info!("Update available: {} -> {}", info.current_version, info.version);
             Ok(Some(info))
         }
@@ -53,23 +74,195 @@ pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, Str
     }
 }
 
-/// Download and install the available update
+/// Force an immediate check, bypassing the skipped-versions/defer-until
+/// gating the background loop applies -- this is the user explicitly asking
+/// right now, not the silent periodic check
+#[tauri::command]
+pub async fn recheck_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    info!("Forcing an immediate update check...");
+    force_check(&app).await
+}
+
+async fn force_check<R: Runtime>(app: &AppHandle<R>) -> Result<Option<UpdateInfo>, String> {
+    let updater = app.updater().map_err(|e| e.to_string())?;
+
+    match updater.check().await {
+        Ok(Some(update)) => {
+            let info = build_info(&update);
+            let _ = app.emit("update:available", info.clone());
+            Ok(Some(info))
+        }
+        Ok(None) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Register an `updater://check-now` listener so any part of the app that
+/// only has an `AppHandle` (a tray item, a global shortcut) can trigger the
+/// same forced check `recheck_for_updates` does, mirroring the event-driven
+/// shape of Tauri's own `tauri://update` flow
+pub fn register_check_now_listener<R: Runtime>(app: &AppHandle<R>) {
+    let handle = app.clone();
+    app.listen("updater://check-now", move |_event| {
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = force_check(&handle).await;
+        });
+    });
+}
+
+fn check_interval_secs<R: Runtime>(app: &AppHandle<R>) -> u64 {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get("check_interval_secs"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_CHECK_INTERVAL_SECS)
+}
+
+fn skipped_versions<R: Runtime>(app: &AppHandle<R>) -> Vec<String> {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get("skipped_versions"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn defer_until_secs<R: Runtime>(app: &AppHandle<R>) -> u64 {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get("defer_until"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
+
+/// Whether `version` should be held back from an `update:available` event:
+/// either the user skipped it outright, or they're still inside a "remind
+/// me later" window
+fn is_suppressed<R: Runtime>(app: &AppHandle<R>, version: &str) -> bool {
+    if skipped_versions(app).iter().any(|skipped| skipped == version) {
+        return true;
+    }
+
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+    defer_until_secs(app) > now
+}
+
+/// Never prompt for this version again
+#[tauri::command]
+pub fn skip_update_version(app: AppHandle, version: String) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+
+    let mut versions = skipped_versions(&app);
+    if !versions.iter().any(|skipped| skipped == &version) {
+        versions.push(version);
+    }
+
+    store.set("skipped_versions", serde_json::json!(versions));
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Suppress `update:available` for the next `duration_secs`
+#[tauri::command]
+pub fn defer_update(app: AppHandle, duration_secs: u64) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+    store.set("defer_until", serde_json::json!(now + duration_secs));
+    store.save().map_err(|e| e.to_string())
+}
+
+fn low_battery_threshold<R: Runtime>(app: &AppHandle<R>) -> u8 {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get("low_battery_threshold"))
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u8)
+        .unwrap_or(DEFAULT_LOW_BATTERY_THRESHOLD)
+}
+
+/// Read the configured low-battery threshold (percent)
+#[tauri::command]
+pub fn get_low_battery_threshold(app: AppHandle) -> u8 {
+    low_battery_threshold(&app)
+}
+
+/// Set the battery percentage below which a download is held back unless `force`
+#[tauri::command]
+pub fn set_low_battery_threshold(app: AppHandle, percent: u8) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    store.set("low_battery_threshold", serde_json::json!(percent));
+    store.save().map_err(|e| e.to_string())
+}
+
+fn is_low_battery<R: Runtime>(app: &AppHandle<R>) -> bool {
+    match power::get_power_status() {
+        Ok(status) if !status.is_ac_power => {
+            status.battery_percentage.map(|percent| percent < low_battery_threshold(app)).unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+/// Download and install the available update. On battery below the
+/// configured threshold this is a no-op unless `force`: an
+/// `update:deferred-low-battery` event is emitted instead, and if
+/// `auto_resume_on_ac` is set the download is retried automatically once
+/// `power:changed` reports AC power restored (see `resume_pending_download_if_any`).
 #[tauri::command]
-pub async fn download_and_install_update(app: AppHandle) -> Result<(), String> {
+pub async fn download_and_install_update(
+    app: AppHandle,
+    force: Option<bool>,
+    auto_resume_on_ac: Option<bool>,
+) -> Result<(), String> {
+    if !force.unwrap_or(false) && is_low_battery(&app) {
+        let threshold = low_battery_threshold(&app);
+        info!("Deferring update download: on battery below {}%", threshold);
+        let _ = app.emit("update:deferred-low-battery", serde_json::json!({ "threshold": threshold }));
+
+        if auto_resume_on_ac.unwrap_or(false) {
+            *PENDING_LOW_BATTERY_DOWNLOAD.lock().unwrap() = true;
+            power::ensure_monitoring_started(&app);
+        }
+
+        return Ok(());
+    }
+
+    *PENDING_LOW_BATTERY_DOWNLOAD.lock().unwrap() = false;
+    perform_download_and_install(&app).await
+}
+
+/// Called by the power monitor when it sees a battery-to-AC transition;
+/// resumes a download that was held back by `download_and_install_update`
+/// for being on low battery, if one is pending
+pub(crate) fn resume_pending_download_if_any<R: Runtime>(app: &AppHandle<R>) {
+    let mut pending = PENDING_LOW_BATTERY_DOWNLOAD.lock().unwrap();
+    if !*pending {
+        return;
+    }
+    *pending = false;
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        info!("AC power restored, resuming deferred update download");
+        let _ = perform_download_and_install(&app).await;
+    });
+}
+
+async fn perform_download_and_install<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     info!("Starting update download...");
-    
+
     let updater = app.updater().map_err(|e| e.to_string())?;
-    
+
     let update = updater.check().await
         .map_err(|e| e.to_string())?
         .ok_or_else(|| "No update available".to_string())?;
-    
+
     let app_handle = app.clone();
-    
+
     // Download with progress reporting
     let mut downloaded = 0u64;
     let total = update.download_size;
-    
+
     let bytes = update.download(
         |chunk_len, content_len| {
             downloaded += chunk_len as u64;
@@ -89,30 +282,47 @@ pub async fn download_and_install_update(app: AppHandle) -> Result<(), String> {
         error!("Failed to download update: {}", e);
         e.to_string()
     })?;
-    
+
     info!("Downloaded {} bytes, installing...", bytes.len());
-    
+
     // Emit installing event
     let _ = app.emit("update:installing", ());
-    
+
     // Install the update - this will restart the app
     update.install(bytes).map_err(|e| {
         error!("Failed to install update: {}", e);
         e.to_string()
     })?;
-    
+
     // Request app restart
     info!("Update installed, restarting...");
     app.restart();
 }
 
-/// Check for updates on startup (silent check, only notifies if update available)
-pub async fn check_updates_on_startup(app: AppHandle) {
+/// Check for updates on startup (silent check, only notifies if an update
+/// is available and isn't skipped/deferred)
+pub async fn check_updates_on_startup<R: Runtime>(app: AppHandle<R>) {
     // Wait a few seconds after startup before checking
     tokio::time::sleep(std::time::Duration::from_secs(3)).await;
-    
+
     info!("Performing startup update check...");
-    
+    gated_check(&app).await;
+}
+
+/// Re-check for updates every `check_interval_secs` (from the update_prefs
+/// store, default 6h) for as long as the app runs. The interval is
+/// re-read each iteration so a runtime change takes effect on the next tick.
+pub fn spawn_periodic_update_check<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(check_interval_secs(&app))).await;
+            info!("Performing periodic update check...");
+            gated_check(&app).await;
+        }
+    });
+}
+
+async fn gated_check<R: Runtime>(app: &AppHandle<R>) {
     let updater = match app.updater() {
         Ok(u) => u,
         Err(e) => {
@@ -120,28 +330,25 @@ pub async fn check_updates_on_startup(app: AppHandle) {
             return;
         }
     };
-    
+
     match updater.check().await {
         Ok(Some(update)) => {
-            let current_version = env!("CARGO_PKG_VERSION").to_string();
-            let info = UpdateInfo {
-                version: update.version.clone(),
-                current_version,
-                body: update.body.clone(),
-                date: update.date.map(|d| d.to_string()),
-            };
-            
-            info!("Update available on startup: {}", info.version);
-            
-            // Emit event to frontend
+            let info = build_info(&update);
+
+            if is_suppressed(app, &info.version) {
+                info!("Update {} is skipped or deferred, not notifying", info.version);
+                return;
+            }
+
+            info!("Update available: {}", info.version);
             let _ = app.emit("update:available", info);
         }
         Ok(None) => {
-            info!("No updates available on startup check");
+            info!("No updates available");
         }
         Err(e) => {
-            // Silent failure on startup - don't bother user
-            warn!("Startup update check failed: {}", e);
+            // Silent failure on background checks - don't bother the user
+            warn!("Update check failed: {}", e);
         }
     }
 }
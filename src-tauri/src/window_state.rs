@@ -0,0 +1,101 @@
+//! Window-state save/restore commands
+//!
+//! `tauri_plugin_window_state` already tracks window geometry internally;
+//! this wraps its `AppHandleExt`/`WindowExt` with commands the frontend can
+//! call directly, a `WindowStateFlags` the IPC boundary can serialize (the
+//! plugin's own `StateFlags` is a raw bitflags value), and a post-restore
+//! monitor-bounds clamp so a window saved on a now-disconnected display
+//! doesn't come back up off-screen.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, Runtime, Window};
+use tauri_plugin_window_state::{AppHandleExt, StateFlags, WindowExt};
+
+/// Which window properties to persist/restore
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowStateFlags {
+    pub position: bool,
+    pub size: bool,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    pub visible: bool,
+    pub always_on_top: bool,
+}
+
+impl Default for WindowStateFlags {
+    fn default() -> Self {
+        Self { position: true, size: true, maximized: true, fullscreen: true, visible: true, always_on_top: true }
+    }
+}
+
+impl From<WindowStateFlags> for StateFlags {
+    fn from(flags: WindowStateFlags) -> Self {
+        let mut result = StateFlags::empty();
+        if flags.position {
+            result |= StateFlags::POSITION;
+        }
+        if flags.size {
+            result |= StateFlags::SIZE;
+        }
+        if flags.maximized {
+            result |= StateFlags::MAXIMIZED;
+        }
+        if flags.fullscreen {
+            result |= StateFlags::FULLSCREEN;
+        }
+        if flags.visible {
+            result |= StateFlags::VISIBLE;
+        }
+        if flags.always_on_top {
+            result |= StateFlags::ALWAYS_ON_TOP;
+        }
+        result
+    }
+}
+
+/// Persist the selected fields of every labeled window to disk
+#[tauri::command]
+pub fn save_window_state(app: AppHandle, flags: Option<WindowStateFlags>) -> Result<(), String> {
+    app.save_window_state(flags.unwrap_or_default().into()).map_err(|e| e.to_string())
+}
+
+/// Restore the selected fields for this window, clamping its position
+/// back onto a currently-connected monitor if the display it was saved on
+/// is now disconnected
+#[tauri::command]
+pub fn restore_window_state(window: Window, flags: Option<WindowStateFlags>) -> Result<(), String> {
+    window.restore_state(flags.unwrap_or_default().into()).map_err(|e| e.to_string())?;
+    clamp_to_monitor(&window)
+}
+
+fn clamp_to_monitor<R: Runtime>(window: &Window<R>) -> Result<(), String> {
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    if monitors.is_empty() {
+        return Ok(());
+    }
+
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+
+    let on_screen = monitors.iter().any(|monitor| {
+        let monitor_pos = monitor.position();
+        let monitor_size = monitor.size();
+        position.x < monitor_pos.x + monitor_size.width as i32
+            && position.x + size.width as i32 > monitor_pos.x
+            && position.y < monitor_pos.y + monitor_size.height as i32
+            && position.y + size.height as i32 > monitor_pos.y
+    });
+
+    if on_screen {
+        return Ok(());
+    }
+
+    // The monitor this window was saved on is gone; drop it onto the
+    // primary monitor instead of leaving it stranded off-screen
+    let fallback = window.primary_monitor().map_err(|e| e.to_string())?.unwrap_or_else(|| monitors[0].clone());
+    let fallback_pos = fallback.position();
+
+    window
+        .set_position(PhysicalPosition::new(fallback_pos.x + 50, fallback_pos.y + 50))
+        .map_err(|e| e.to_string())
+}